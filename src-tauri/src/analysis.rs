@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use crate::settings::load_setting;
+use crate::parsers::{is_result_line, ParserRegistry, JS_NAME_SEPARATOR};
+use crate::names::{load_name_mappings, mappings_for_repo, LogNames, NameMapper, NameMapping, NameResolution};
+use crate::rules::{compute_verdict, load_rule_configs, log_name_of, AnalysisContext, RuleConfig, RuleRegistry, Severity};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::cmp::min;
+use std::collections::BTreeMap;
 
 // Compile regex patterns once at module level to avoid repeated compilation
 lazy_static! {
@@ -60,6 +64,16 @@ lazy_static! {
     // Nextest format patterns - handles "PASS [duration] test_name" and "FAIL [duration] test_name"
     static ref NEXTEST_PASS_RE: Regex = Regex::new(r"(?i)\s*PASS\s+\[[^\]]+\]\s+(.+?)\s*$").unwrap();
     static ref NEXTEST_FAIL_RE: Regex = Regex::new(r"(?i)\s*FAIL\s+\[[^\]]+\]\s+(.+?)\s*$").unwrap();
+    // nextest with retries: "TRY 2 PASS [   0.01s] crate test", "RETRY 2/3 [ ] crate test", "FLAKY 2/3 [   0.01s] crate test"
+    static ref NEXTEST_TRY_RE: Regex = Regex::new(r"^\s*TRY\s+(\d+)\s+([A-Z]+)\s+\[[^\]]*\]\s+(.+?)\s*$").unwrap();
+    static ref NEXTEST_RETRY_RE: Regex = Regex::new(r"^\s*RETRY\s+\d+/\d+\s+\[[^\]]*\]\s+(.+?)\s*$").unwrap();
+    static ref NEXTEST_FLAKY_RE: Regex = Regex::new(r"^\s*FLAKY\s+(\d+)/\d+\s+\[[^\]]*\]\s+(.+?)\s*$").unwrap();
+    // "TIMEOUT [  60.003s] crate test" ends a hung test; "SLOW [> 60.000s] crate test" reports one still running
+    static ref NEXTEST_TIMEOUT_RE: Regex = Regex::new(r"^\s*TIMEOUT\s+\[[^\]]+\]\s+(.+?)\s*$").unwrap();
+    static ref NEXTEST_SLOW_RE: Regex = Regex::new(r"^\s*SLOW\s+\[[^\]]+\]\s+(.+?)\s*$").unwrap();
+    static ref NEXTEST_TIME_RE: Regex = Regex::new(r"\[\s*>?\s*([\d.]+)s\]").unwrap();
+    // libtest --report-time: "test tests::a ... ok <0.012s>"
+    static ref LIBTEST_REPORT_TIME_RE: Regex = Regex::new(r"\btest\s+(.+?)\s+\.\.\.\s+(?:ok|FAILED|ignored)\s+<([\d.]+)s>").unwrap();
     static ref NEXTEST_SKIP_RE: Regex = Regex::new(r"(?i)\s*(SKIP|IGNORED)\s+\[[^\]]+\]\s+(.+?)\s*$").unwrap();
     
     // START pattern for nextest - captures test names from START lines
@@ -79,6 +93,422 @@ lazy_static! {
     
     // Pattern for tests that have diagnostic info after the "..." but before status
     static ref TEST_WITH_DIAGNOSTICS_RE: Regex = Regex::new(r"(?i)\btest\s+(.+?)\s+\.\.\.\s*(?:error:|$)").unwrap();
+
+    // Captured output: libtest "---- tests::foo stdout ----", nextest "--- STDOUT: crate tests::foo ---"
+    static ref OUTPUT_BLOCK_RE: Regex = Regex::new(r"^-{3,}\s*(?:(\S+) std(?:out|err) -{3,}|STD(?:OUT|ERR):\s+(.+?)(?:\s+-{3,})?)\s*$").unwrap();
+    // "thread 'tests::foo' panicked at src/lib.rs:20:9:" with the message on the next lines (Rust 1.73+),
+    // or "thread 'tests::foo' panicked at 'message', src/lib.rs:20:9" before that
+    static ref PANIC_RE: Regex = Regex::new(r"thread '([^']+)' panicked at (\S+?:\d+:\d+):\s*(.*)$").unwrap();
+    static ref PANIC_OLD_RE: Regex = Regex::new(r"thread '([^']+)' panicked at '(.*)', (\S+?:\d+:\d+)\s*$").unwrap();
+
+    // rustc diagnostics and cargo's summary; a bare "error: test failed" only means tests failed
+    static ref COMPILE_ERROR_RE: Regex = Regex::new(r"^error\[E\d{4}\]: |^error: could not compile |^error: aborting due to ").unwrap();
+    // Build or collection failures of the other runners (go, Maven, Gradle, tsc, pytest)
+    static ref BUILD_ERROR_RE: Regex = Regex::new(r"\[(?:build|setup) failed\]$|COMPILATION ERROR|Compilation failure|^Compilation failed|error TS\d+: |^ERROR collecting |ImportError while importing test module").unwrap();
+    // Totals the runner prints: libtest once per binary, nextest once per run
+    static ref LIBTEST_SUMMARY_RE: Regex = Regex::new(r"test result: (?:ok|FAILED)\. (\d+) passed; (\d+) failed; (\d+) ignored;(?:.*finished in ([\d.]+)s)?").unwrap();
+    static ref NEXTEST_SUMMARY_RE: Regex = Regex::new(r"^\s*Summary \[\s*([\d.]+)s\]\s+\d+ tests? run: (.*)$").unwrap();
+    static ref NEXTEST_SUMMARY_COUNT_RE: Regex = Regex::new(r"(\d+) (passed|failed|skipped|timed out)").unwrap();
+    // A test binary or the whole run killed before it could finish, as cargo, the Rust runtime,
+    // the shell or the OS report it (never a test name that merely mentions these words)
+    static ref ABORT_RE: Regex = Regex::new(r"\(signal: \d+, SIG\w+\)|^fatal runtime error: |^thread '.*' has overflowed its stack|(?:^|\d+ )Segmentation fault\b|^(?i:killed)$|^memory allocation of \d+ bytes failed|^fatal error: runtime: out of memory|^Out of memory: Kill").unwrap();
+
+    // Test binary a libtest block belongs to
+    static ref RUNNING_BINARY_RE: Regex = Regex::new(r"^\s*Running\s+(?:.*\()?([^\s()]+?)\)?\s*$").unwrap();
+    static ref DOC_TESTS_RE: Regex = Regex::new(r"^\s*Doc-tests\s+(\S+)\s*$").unwrap();
+    static ref BINARY_HASH_RE: Regex = Regex::new(r"-[0-9a-f]{8,}$").unwrap();
+
+    // Doctest names: "src/lib.rs - Foo::bar (line 42)", optionally followed by " - compile fail" and the like
+    static ref DOCTEST_NAME_RE: Regex = Regex::new(r"^(\S+\.rs) - (?:(.+?) )?\(line (\d+)\)(?: - (.+))?$").unwrap();
+}
+
+/// Lines of captured output kept in `FailureDetail::stdout`, counted from the end
+const FAILURE_OUTPUT_LINES: usize = 20;
+
+/// Settings key holding the number of seconds above which an F2P/P2P test counts as too slow
+pub const SLOW_TEST_THRESHOLD_SETTING: &str = "slow_test_threshold_secs";
+pub const DEFAULT_SLOW_TEST_THRESHOLD_SECS: f64 = 60.0;
+
+// Slowest tests listed in the report, besides every test over the threshold
+const SLOW_TEST_LIMIT: usize = 10;
+
+// Stable rule identifiers, used as keys in `AnalysisReport::rule_checks`
+pub const C1_FAILED_IN_BASE_PRESENT_IN_P2P: &str = "c1_failed_in_base_present_in_P2P";
+pub const C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P: &str = "c2_failed_in_after_present_in_F2P_or_P2P";
+pub const C3_F2P_SUCCESS_IN_BEFORE: &str = "c3_F2P_success_in_before";
+pub const C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE: &str = "c4_P2P_missing_in_base_and_not_passing_in_before";
+pub const C5_DUPLICATES_IN_SAME_LOG_FOR_F2P_OR_P2P: &str = "c5_duplicates_in_same_log_for_F2P_or_P2P";
+pub const C6_TEST_MARKED_FAILED_IN_REPORT_BUT_PASSING_IN_AGENT: &str = "c6_test_marked_failed_in_report_but_passing_in_agent";
+pub const C7_F2P_TESTS_IN_GOLDEN_SOURCE_DIFF: &str = "c7_f2p_tests_in_golden_source_diff";
+pub const C8_F2P_FAILING_IN_BEFORE_FOR_WRONG_REASON: &str = "c8_F2P_failing_in_before_for_wrong_reason";
+pub const C9_BUILD_FAILED_IN_LOG: &str = "c9_build_failed_in_log";
+pub const C10_FLAKY_IN_ANY_LOG_PRESENT_IN_F2P_OR_P2P: &str = "c10_flaky_in_any_log_present_in_F2P_or_P2P";
+pub const C11_F2P_OR_P2P_SLOWER_THAN_THRESHOLD: &str = "c11_F2P_or_P2P_slower_than_threshold";
+
+/// Schema version of `AnalysisReport`. Bump whenever a field is renamed, removed
+/// or changes meaning so downstream consumers can detect incompatible output.
+pub const ANALYSIS_REPORT_VERSION: u32 = 1;
+
+/// Result of `analyze_logs`. Serializes to the same JSON layout the frontend
+/// has always consumed, plus a `version` field.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub version: u32,
+    pub inputs: ReportInputs,
+    pub counts: TestCounts,
+    pub rule_checks: BTreeMap<String, RuleCheck>,
+    pub rejection_reason: RejectionReason,
+    pub p2p_analysis: BTreeMap<String, TestStatusRow>,
+    pub f2p_analysis: BTreeMap<String, TestStatusRow>,
+    pub debug_log_counts: Vec<LogCounts>,
+    pub verdict: Verdict,
+    // Per-test explanation of every log status, only present when tracing was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<BTreeMap<String, Vec<StatusTrace>>>,
+    // Logs whose parsed counts disagree with the runner's summary lines
+    #[serde(default)]
+    pub parser_health: Vec<String>,
+    // F2P/P2P names that several test binaries reported under the same name
+    #[serde(default)]
+    pub binary_collisions: Vec<BinaryCollision>,
+    // F2P/P2P names matched to a differently written log name, or left missing as ambiguous
+    #[serde(default)]
+    pub name_resolutions: Vec<NameResolution>,
+    // Slowest F2P/P2P tests with the time they took in before and after, slowest first
+    #[serde(default)]
+    pub slow_tests: Vec<SlowTest>,
+}
+
+/// Seconds an F2P/P2P test took in before and after, where the log reports it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SlowTest {
+    pub test: String,
+    // "f2p" or "p2p"
+    pub kind: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+    pub over_threshold: bool,
+}
+
+impl AnalysisReport {
+    pub fn rule(&self, id: &str) -> Option<&RuleCheck> {
+        self.rule_checks.get(id)
+    }
+
+    pub fn has_problem(&self, id: &str) -> bool {
+        self.rule(id).map(|r| r.has_problem).unwrap_or(false)
+    }
+
+    /// Ids of every rule that reported a problem, in key order
+    pub fn violated_rules(&self) -> Vec<&str> {
+        self.rule_checks.iter()
+            .filter(|(_, check)| check.has_problem)
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportInputs {
+    pub base_log: String,
+    pub before_log: String,
+    pub after_log: String,
+    pub agent_log: String, // empty when no agent log was provided
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCounts {
+    #[serde(rename = "P2P")]
+    pub p2p: usize,
+    #[serde(rename = "F2P")]
+    pub f2p: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleCheck {
+    pub has_problem: bool,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default = "default_severity")]
+    pub severity: Severity,
+    #[serde(default = "crate::rules::default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub example_limit: Option<usize>,
+    #[serde(default)]
+    pub examples: Vec<String>,
+    // Plain names of the offending tests, not truncated by `example_limit`
+    #[serde(default)]
+    pub tests: Vec<String>,
+    // Only C5 reports its findings grouped by log
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_examples_per_log: Option<BTreeMap<String, Vec<String>>>,
+    // Status lines backing each offending test, so the UI can jump to them
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    // Rule whose finding made this one meaningless, e.g. a build failure in a log it reads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed_by: Option<String>,
+}
+
+/// A status line in one of the logs that proves a rule violation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Evidence {
+    pub test: String,
+    pub log: String, // "base", "before", "after" or "agent"
+    pub line_number: usize, // 1-based
+    pub line_content: String,
+    pub context_before: Vec<String>,
+    pub context_after: Vec<String>,
+}
+
+fn default_severity() -> Severity {
+    Severity::Error
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Decision {
+    Accept,
+    Reject,
+    NeedsManualReview,
+}
+
+/// Overall decision combined from every enabled rule, see `rules::compute_verdict`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Verdict {
+    pub decision: Decision,
+    pub reasons: Vec<VerdictReason>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerdictReason {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub message: String,
+    pub tests: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectionReason {
+    pub satisfied: bool,
+    pub p2p_ignored_because_passed_in_base_and_after: Vec<String>,
+    pub p2p_considered: Vec<String>,
+    pub p2p_rejected: Vec<String>,
+    pub p2p_considered_but_ok: Vec<String>,
+    pub f2p_ignored_because_passed_in_after: Vec<String>,
+    pub f2p_considered: Vec<String>,
+    pub f2p_rejected: Vec<String>,
+    pub f2p_considered_but_ok: Vec<String>,
+}
+
+/// A test name reported by more than one binary in one log, with each binary's status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BinaryCollision {
+    pub test: String,
+    pub log: String,
+    pub binaries: BTreeMap<String, String>,
+}
+
+/// How the status of one test in one log was derived
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusTrace {
+    pub log: String,
+    pub status: String,
+    pub parser: String,
+    // Final precedence decision, e.g. "failed and passed both recorded; failed takes precedence"
+    pub decision: String,
+    pub events: Vec<ParseEvent>,
+}
+
+/// A single parser match (or rejected candidate) for a test
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParseEvent {
+    pub test: String,
+    pub parser: String,
+    pub pattern: String,
+    pub status: String,
+    pub line_number: Option<usize>, // 1-based
+    pub line: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+// Collects parse events while tracing is on, a no-op otherwise
+#[derive(Debug, Default)]
+pub(crate) struct TraceRecorder {
+    enabled: bool,
+    parser: &'static str,
+    events: Vec<ParseEvent>,
+}
+
+impl TraceRecorder {
+    pub(crate) fn new(enabled: bool, parser: &'static str) -> Self {
+        TraceRecorder { enabled, parser, events: Vec::new() }
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn record(&mut self, pattern: &str, test: &str, status: &str, line_number: Option<usize>, line: &str) {
+        self.push(pattern, test, status, line_number, line, None);
+    }
+
+    // Records a candidate match together with the reason it was accepted or skipped
+    pub(crate) fn note(&mut self, pattern: &str, test: &str, status: &str, line_number: Option<usize>, line: &str, note: &str) {
+        self.push(pattern, test, status, line_number, line, Some(note.to_string()));
+    }
+
+    fn push(&mut self, pattern: &str, test: &str, status: &str, line_number: Option<usize>, line: &str, note: Option<String>) {
+        if !self.enabled {
+            return;
+        }
+        self.events.push(ParseEvent {
+            test: test.to_string(),
+            parser: self.parser.to_string(),
+            pattern: pattern.to_string(),
+            status: status.to_lowercase(),
+            line_number,
+            line: line.trim_end().to_string(),
+            note,
+        });
+    }
+
+    pub(crate) fn into_events(self) -> Vec<ParseEvent> {
+        self.events
+    }
+}
+
+// 1-based line number and content of the line holding byte `offset`
+pub(crate) fn line_at(text: &str, offset: usize) -> (usize, &str) {
+    let start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let end = text[offset..].find('\n').map(|i| offset + i).unwrap_or(text.len());
+    (text[..start].matches('\n').count() + 1, &text[start..end])
+}
+
+/// Status of a single test in every log: "passed", "failed", "ignored" or "missing"
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestStatusRow {
+    pub base: String,
+    pub before: String,
+    pub after: String,
+    pub agent: String,
+    pub report: String,
+    // Why the test failed, keyed by the logs it failed in ("base", "before", "after", "agent")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub failures: BTreeMap<String, FailureDetail>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub doctest: bool,
+}
+
+/// A documentation example, identified by its file and item. The line moves whenever the file
+/// above the example is edited, so it is not part of the identity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Doctest {
+    pub file: String,
+    // Empty for crate-level examples
+    pub item: String,
+    pub line: u32,
+    // Rustdoc's mode suffix, e.g. "compile fail"
+    pub variant: Option<String>,
+}
+
+impl Doctest {
+    pub fn same_example(&self, other: &Doctest) -> bool {
+        self.file == other.file && self.item == other.item && self.variant == other.variant
+    }
+}
+
+/// Splits a rustdoc test name into file, item path and line, `None` for other tests
+pub fn parse_doctest_name(name: &str) -> Option<Doctest> {
+    let captures = DOCTEST_NAME_RE.captures(name.trim())?;
+    Some(Doctest {
+        file: captures[1].to_string(),
+        item: captures.get(2).map(|item| item.as_str().to_string()).unwrap_or_default(),
+        line: captures[3].parse().ok()?,
+        variant: captures.get(4).map(|variant| variant.as_str().to_string()),
+    })
+}
+
+/// Panic site, message and the tail of the captured output of a failed test
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FailureDetail {
+    #[serde(default)]
+    pub kind: FailureKind,
+    pub location: Option<String>, // "src/lib.rs:20:9"
+    pub message: Option<String>,
+    pub stdout: String,
+}
+
+/// Why a test failed. Only assertions and panics show that the test exercised the code under test.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    Assertion,
+    Panic,
+    CompileError,
+    Timeout,
+    Infrastructure,
+    // Nothing in the log tells the failure apart
+    #[default]
+    Unknown,
+}
+
+impl FailureKind {
+    pub fn is_genuine(self) -> bool {
+        matches!(self, FailureKind::Assertion | FailureKind::Panic)
+    }
+
+    /// Same spelling as the serialized form
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FailureKind::Assertion => "assertion",
+            FailureKind::Panic => "panic",
+            FailureKind::CompileError => "compile_error",
+            FailureKind::Timeout => "timeout",
+            FailureKind::Infrastructure => "infrastructure",
+            FailureKind::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogCounts {
+    pub label: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub all: usize,
+    // Parser chosen by format detection and its confidence, with every candidate's score
+    #[serde(default)]
+    pub parser: String,
+    #[serde(default)]
+    pub confidence: f32,
+    #[serde(default)]
+    pub detection_scores: BTreeMap<String, f32>,
+    #[serde(default)]
+    pub overridden: bool,
+    // Whether the run completed, and the line that says otherwise
+    #[serde(default)]
+    pub outcome: LogOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome_reason: Option<String>,
+    // Totals from the runner's own summary lines, when the log has them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<SuiteSummary>,
+}
+
+/// How the run behind a log ended
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogOutcome {
+    #[default]
+    Completed,
+    // Nothing ran because the code or the tests did not compile
+    BuildFailed,
+    // The run was killed, crashed or produced no results at all
+    Aborted,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -557,7 +987,7 @@ pub struct TestLists {
     pub pass_to_pass: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchResult {
     pub line_number: usize,
     pub line_content: String,
@@ -620,10 +1050,12 @@ pub fn search_logs(file_paths: Vec<String>, test_name: String) -> Result<LogSear
     println!("Searching logs for test: {}", test_name);
     
     // Find log files
-    let base_log = file_paths.iter().find(|path| path.to_lowercase().contains("base.log"));
-    let before_log = file_paths.iter().find(|path| path.to_lowercase().contains("before.log"));
-    let after_log = file_paths.iter().find(|path| path.to_lowercase().contains("after.log"));
-    let agent_log = file_paths.iter().find(|path| path.to_lowercase().contains("post_agent_patch.log") || path.to_lowercase().contains("agent.log"));
+    // Text logs read better than XML, so JUnit reports are only searched when a role has no log
+    let roles = detect_file_roles(&file_paths);
+    let base_log = roles.base_log.as_ref().or(roles.base_junit.as_ref());
+    let before_log = roles.before_log.as_ref().or(roles.before_junit.as_ref());
+    let after_log = roles.after_log.as_ref().or(roles.after_junit.as_ref());
+    let agent_log = roles.agent_log.as_ref().or(roles.agent_junit.as_ref());
     
     let base_results = if let Some(path) = base_log {
         search_in_log_file(path, &test_name)?
@@ -660,12 +1092,155 @@ pub fn search_logs(file_paths: Vec<String>, test_name: String) -> Result<LogSear
     })
 }
 
-pub async fn analyze_logs(file_paths: Vec<String>) -> Result<serde_json::Value, String> {
+/// Which downloaded file plays which role in a deliverable
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DeliverableFiles {
+    pub main_json: Option<String>,
+    pub base_log: Option<String>,
+    pub before_log: Option<String>,
+    pub after_log: Option<String>,
+    pub agent_log: Option<String>,
+    pub report_json: Option<String>,
+    // JUnit XML reports, an alternative to the text log of the same role
+    #[serde(default)]
+    pub base_junit: Option<String>,
+    #[serde(default)]
+    pub before_junit: Option<String>,
+    #[serde(default)]
+    pub after_junit: Option<String>,
+    #[serde(default)]
+    pub agent_junit: Option<String>,
+    pub unrecognized: Vec<String>,
+}
+
+impl DeliverableFiles {
+    /// Names the required roles that no file was found for
+    pub fn missing_required(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.main_json.is_none() { missing.push("main.json"); }
+        if self.source("base").is_none() { missing.push("base.log"); }
+        if self.source("before").is_none() { missing.push("before.log"); }
+        if self.source("after").is_none() { missing.push("after.log"); }
+        missing
+    }
+
+    /// File to read the results of a log role from; a JUnit report takes precedence over the text log
+    pub fn source(&self, role: &str) -> Option<&String> {
+        let (log, junit) = match role {
+            "base" => (&self.base_log, &self.base_junit),
+            "before" => (&self.before_log, &self.before_junit),
+            "after" => (&self.after_log, &self.after_junit),
+            "agent" => (&self.agent_log, &self.agent_junit),
+            _ => return None,
+        };
+        junit.as_ref().or(log.as_ref())
+    }
+}
+
+// Role of a JUnit XML file, from its name ("instance_after.xml", "before_junit.xml") or,
+// for a bare "junit.xml", from the folder it sits in ("logs/base/junit.xml")
+fn junit_role(path: &str) -> Option<&'static str> {
+    let lower = path.to_lowercase().replace('\\', "/");
+    let mut components = lower.rsplit('/');
+    let stem = components.next()?.strip_suffix(".xml")?;
+    let stem = stem.trim_end_matches("junit").trim_end_matches(['.', '_', '-']);
+    let stem = if stem.is_empty() { components.next()? } else { stem };
+    // The role is a whole "_"/"-" separated suffix, as for the text logs, so "database.xml" is no base
+    let names_role = |role: &str| {
+        stem == role || [format!("_{}", role), format!("-{}", role)].iter().any(|suffix| stem.ends_with(suffix.as_str()))
+    };
+    if names_role("post_agent_patch") || names_role("agent") {
+        Some("agent")
+    } else if names_role("after") {
+        Some("after")
+    } else if names_role("before") {
+        Some("before")
+    } else if names_role("base") {
+        Some("base")
+    } else {
+        None
+    }
+}
+
+/// Assigns each path to a deliverable role; the first match wins for every role
+pub fn detect_file_roles(file_paths: &[String]) -> DeliverableFiles {
+    let mut roles = DeliverableFiles::default();
+
+    for path in file_paths {
+        let lower = path.to_lowercase();
+        let slot = if let Some(role) = junit_role(path) {
+            match role {
+                "base" => &mut roles.base_junit,
+                "before" => &mut roles.before_junit,
+                "after" => &mut roles.after_junit,
+                _ => &mut roles.agent_junit,
+            }
+        } else if lower.contains("base.log") {
+            &mut roles.base_log
+        } else if lower.contains("before.log") {
+            &mut roles.before_log
+        } else if lower.contains("after.log") && !lower.contains("post_agent_patch") {
+            &mut roles.after_log
+        } else if lower.contains("post_agent_patch.log") || lower.contains("agent.log") {
+            &mut roles.agent_log
+        } else if lower.contains("results/report.json") || lower.ends_with("report.json") {
+            &mut roles.report_json
+        } else if lower.contains("main.json") || lower.contains("main/") {
+            &mut roles.main_json
+        } else {
+            roles.unrecognized.push(path.clone());
+            continue;
+        };
+        if slot.is_none() {
+            *slot = Some(path.clone());
+        } else {
+            roles.unrecognized.push(path.clone());
+        }
+    }
+
+    roles
+}
+
+/// Knobs for a single `analyze_logs_with_options` run
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisOptions {
+    /// Per-rule configuration; `None` loads it from settings
+    pub rule_configs: Option<BTreeMap<String, RuleConfig>>,
+    /// Record how every F2P/P2P status was derived and return it in `AnalysisReport::trace`
+    pub trace: bool,
+    /// Parser name per log role ("base", "before", "after", "agent"), bypassing format detection
+    pub parser_overrides: BTreeMap<String, String>,
+    /// Name mappings keyed by repository; `None` loads them from settings
+    pub name_mappings: Option<BTreeMap<String, Vec<NameMapping>>>,
+    /// Seconds above which an F2P/P2P test is flagged as slow; `None` loads it from settings
+    pub slow_test_threshold: Option<f64>,
+}
+
+/// Slow-test threshold from settings, the default when unset or unreadable
+pub fn load_slow_test_threshold() -> f64 {
+    match load_setting(SLOW_TEST_THRESHOLD_SETTING.to_string()) {
+        Ok(raw) if !raw.trim().is_empty() => raw.trim().parse().unwrap_or_else(|e| {
+            println!(
+                "Invalid {} setting '{}', using {}s: {}",
+                SLOW_TEST_THRESHOLD_SETTING, raw, DEFAULT_SLOW_TEST_THRESHOLD_SECS, e
+            );
+            DEFAULT_SLOW_TEST_THRESHOLD_SECS
+        }),
+        _ => DEFAULT_SLOW_TEST_THRESHOLD_SECS,
+    }
+}
+
+pub async fn analyze_logs(file_paths: Vec<String>) -> Result<AnalysisReport, String> {
+    analyze_logs_with_options(file_paths, AnalysisOptions::default()).await
+}
+
+pub async fn analyze_logs_with_options(file_paths: Vec<String>, options: AnalysisOptions) -> Result<AnalysisReport, String> {
     println!("Starting log analysis with file paths: {:?}", file_paths);
     
+    let roles = detect_file_roles(&file_paths);
+
     // Find and parse main.json
-    let main_json_path = file_paths.iter()
-        .find(|path| path.to_lowercase().contains("main.json") || path.to_lowercase().contains("main/"))
+    let main_json_path = roles.main_json.as_ref()
         .ok_or("main.json file not found in provided paths".to_string())?;
     
     println!("Found main.json at: {}", main_json_path);
@@ -697,30 +1272,34 @@ pub async fn analyze_logs(file_paths: Vec<String>) -> Result<serde_json::Value,
     println!("Found {} fail_to_pass tests and {} pass_to_pass tests", 
              fail_to_pass.len(), pass_to_pass.len());
     
-    // Find log files
-    let base_log = file_paths.iter().find(|path| path.to_lowercase().contains("base.log"));
-    let before_log = file_paths.iter().find(|path| path.to_lowercase().contains("before.log"));
-    let after_log = file_paths.iter().find(|path| path.to_lowercase().contains("after.log"));
-    let agent_log = file_paths.iter().find(|path| path.to_lowercase().contains("post_agent_patch.log") || path.to_lowercase().contains("agent.log"));
+    // Find log files, preferring a JUnit XML report of the same role
+    let base_log = roles.source("base");
+    let before_log = roles.source("before");
+    let after_log = roles.source("after");
+    let agent_log = roles.source("agent");
     
     if base_log.is_none() || before_log.is_none() || after_log.is_none() {
         return Err("Missing required log files (base.log, before.log, after.log)".to_string());
     }
     
     // Parse log files using the Rust test parser logic
-    let base_parsed = parse_rust_log_file(base_log.unwrap())?;
-    let before_parsed = parse_rust_log_file(before_log.unwrap())?;
-    let after_parsed = parse_rust_log_file(after_log.unwrap())?;
+    let overrides = &options.parser_overrides;
+    if let Some(role) = overrides.keys().find(|role| !["base", "before", "after", "agent"].contains(&role.as_str())) {
+        return Err(format!("Unknown log role '{}' in parser overrides", role));
+    }
+    let base_parsed = parse_log_file(base_log.unwrap(), options.trace, overrides.get("base"))?;
+    let before_parsed = parse_log_file(before_log.unwrap(), options.trace, overrides.get("before"))?;
+    let after_parsed = parse_log_file(after_log.unwrap(), options.trace, overrides.get("after"))?;
     
     // Parse agent log if available
     let agent_parsed = if let Some(agent_path) = agent_log {
-        Some(parse_rust_log_file(agent_path)?)
+        Some(parse_log_file(agent_path, options.trace, overrides.get("agent"))?)
     } else {
         None
     };
     
     // Find and parse report.json if available
-    let report_json_path = file_paths.iter().find(|path| path.to_lowercase().contains("results/report.json") || path.to_lowercase().ends_with("report.json"));
+    let report_json_path = roles.report_json.as_ref();
     let report_data = if let Some(report_path) = report_json_path {
         println!("Found report.json at: {}", report_path);
         match fs::read_to_string(report_path) {
@@ -743,8 +1322,29 @@ pub async fn analyze_logs(file_paths: Vec<String>) -> Result<serde_json::Value,
         None
     };
     
+    // Per-rule configuration from settings; fall back to rule defaults if it can't be read
+    let rule_configs = match options.rule_configs {
+        Some(rule_configs) => rule_configs,
+        None => load_rule_configs().unwrap_or_else(|e| {
+            println!("Failed to load rule config, using defaults: {}", e);
+            BTreeMap::new()
+        }),
+    };
+    
+    // Repository-specific name mappings from settings, for main.json's repo
+    let name_mappings = match options.name_mappings {
+        Some(name_mappings) => name_mappings,
+        None => load_name_mappings().unwrap_or_else(|e| {
+            println!("Failed to load name mappings, using none: {}", e);
+            BTreeMap::new()
+        }),
+    };
+    let repo = main_json.get("repo").and_then(|v| v.as_str());
+    let mapper = NameMapper::new(&mappings_for_repo(&name_mappings, repo));
+    let slow_test_threshold = options.slow_test_threshold.unwrap_or_else(load_slow_test_threshold);
+    
     // Generate analysis result similar to swebench-log-analyzer-rust
-    let analysis_result = generate_analysis_result(
+    let mut analysis_result = generate_analysis_result(
         &base_parsed,
         &before_parsed, 
         &after_parsed,
@@ -756,9 +1356,21 @@ pub async fn analyze_logs(file_paths: Vec<String>) -> Result<serde_json::Value,
         after_log.unwrap(),
         agent_log,
         report_data.as_ref(),
-        &file_paths
+        &file_paths,
+        &rule_configs,
+        &mapper,
+        slow_test_threshold,
     );
     
+    if options.trace {
+        let mut logs = vec![("base", &base_parsed), ("before", &before_parsed), ("after", &after_parsed)];
+        if let Some(agent_parsed) = agent_parsed.as_ref() {
+            logs.push(("agent", agent_parsed));
+        }
+        let universe: Vec<String> = pass_to_pass.iter().chain(fail_to_pass.iter()).cloned().collect();
+        analysis_result.trace = Some(build_status_trace(&universe, &logs, &mapper));
+    }
+    
     Ok(analysis_result)
 }
 
@@ -768,12 +1380,18 @@ fn search_in_log_file(file_path: &str, test_name: &str) -> Result<Vec<SearchResu
     let content = fs::read_to_string(file_path)
         .map_err(|e| format!("Failed to read log file {}: {}", file_path, e))?;
     
+    let results = search_in_log_content(&content, test_name);
+    
+    println!("Found {} matches in {}", results.len(), file_path);
+    Ok(results)
+}
+
+pub(crate) fn search_in_log_content(content: &str, test_name: &str) -> Vec<SearchResult> {
     let lines: Vec<&str> = content.lines().collect();
     let mut results = Vec::new();
     
     // Prepare search terms
     let search_terms = get_search_terms(test_name);
-    println!("Search terms for '{}': {:?}", test_name, search_terms);
     
     // Search for lines containing any of the search terms
     for (line_number, line) in lines.iter().enumerate() {
@@ -786,6 +1404,9 @@ fn search_in_log_file(file_path: &str, test_name: &str) -> Result<Vec<SearchResu
                 break;
             }
         }
+        if !found_match {
+            found_match = js_title_in_suite(&lines, line_number, test_name);
+        }
         
         if found_match {
             let context_before: Vec<String> = lines.iter()
@@ -809,8 +1430,69 @@ fn search_in_log_file(file_path: &str, test_name: &str) -> Result<Vec<SearchResu
         }
     }
     
-    println!("Found {} matches in {}", results.len(), file_path);
-    Ok(results)
+    results
+}
+
+/// Whether a log line reports a test outcome (libtest "... ok", UI paths, nextest PASS/FAIL/SKIP/TIMEOUT
+/// or a result line of one of the other runners in `parsers`)
+pub(crate) fn is_status_line(line: &str) -> bool {
+    let line = strip_ansi_color_codes(line);
+    (line.contains("...") && STATUS_RE.is_match(&line))
+        || NEXTEST_PASS_RE.is_match(&line)
+        || NEXTEST_FAIL_RE.is_match(&line)
+        || NEXTEST_SKIP_RE.is_match(&line)
+        || NEXTEST_TIMEOUT_RE.is_match(&line)
+        || is_result_line(line.trim())
+}
+
+/// Whether a status line names this exact test, not merely one whose name starts or ends with it
+/// (`tests::foo` must not pick up `tests::foo_bar` or `other_tests::foo`)
+pub(crate) fn line_names_test(line: &str, test_name: &str) -> bool {
+    let line = strip_ansi_color_codes(line);
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    get_search_terms(test_name).iter().any(|term| {
+        line.match_indices(term.as_str()).any(|(start, _)| {
+            let before = line[..start].chars().next_back();
+            let after = line[start + term.len()..].chars().next();
+            !before.is_some_and(is_word) && !after.is_some_and(|c| is_word(c) || c == ':')
+        })
+    })
+}
+
+/// The `it` title of a JavaScript "describe > it" name
+pub(crate) fn js_test_title(test_name: &str) -> Option<&str> {
+    test_name.rsplit_once(JS_NAME_SEPARATOR).map(|(_, title)| title)
+}
+
+// Whether this line holds the bare `it` title of a JavaScript "describe > it" name under its own
+// describe blocks. Jest, Vitest and Mocha print each title on its own line, a parent being the
+// nearest less indented line above, so "B > renders header" is not found under "A".
+fn js_title_in_suite(lines: &[&str], index: usize, test_name: &str) -> bool {
+    let mut titles: Vec<&str> = test_name.split(JS_NAME_SEPARATOR).collect();
+    let title = titles.pop().unwrap_or_default();
+    if titles.is_empty() || !line_names_test(lines[index], title) {
+        return false;
+    }
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let mut indent = indent_of(&strip_ansi_color_codes(lines[index]));
+    for above in lines[..index].iter().rev() {
+        let above = strip_ansi_color_codes(above);
+        let text = above.trim();
+        if text.is_empty() || indent_of(&above) >= indent {
+            continue;
+        }
+        // Vitest marks suite lines and counts their tests: "✓ math (3)"
+        let text = text.trim_start_matches(['✓', '✔', '√', '✕', '✖', '×', '↓']).trim_start();
+        let parent = titles.pop().unwrap_or_default();
+        if !text.strip_prefix(parent).is_some_and(|rest| rest.is_empty() || rest.starts_with(" (")) {
+            return false;
+        }
+        if titles.is_empty() {
+            return true;
+        }
+        indent = indent_of(&above);
+    }
+    false
 }
 
 fn get_search_terms(test_name: &str) -> Vec<String> {
@@ -830,20 +1512,166 @@ fn get_search_terms(test_name: &str) -> Vec<String> {
     search_terms
 }
 
-#[derive(Debug)]
-struct ParsedLog {
-    passed: std::collections::HashSet<String>,
-    failed: std::collections::HashSet<String>,
-    ignored: std::collections::HashSet<String>,
-    all: std::collections::HashSet<String>,
+#[derive(Debug, Default)]
+pub struct ParsedLog {
+    pub passed: std::collections::HashSet<String>,
+    pub failed: std::collections::HashSet<String>,
+    pub ignored: std::collections::HashSet<String>,
+    pub all: std::collections::HashSet<String>,
+    // Which parser produced this result, its detection confidence and every parser's score
+    pub parser: String,
+    pub confidence: f32,
+    pub detection_scores: BTreeMap<String, f32>,
+    // Set when the parser was picked by a manual override instead of detection
+    pub overridden: bool,
+    // Parse events, empty unless the log was parsed with tracing on
+    pub trace: Vec<ParseEvent>,
+    // Seconds per test and captured output, for formats that report them
+    pub durations: std::collections::HashMap<String, f64>,
+    pub stdout: std::collections::HashMap<String, String>,
+    // Totals a test binary reported for itself, one entry per suite
+    pub suites: Vec<SuiteSummary>,
+    // Panic and output details of failed tests, where the log has them
+    pub failures: std::collections::HashMap<String, FailureDetail>,
+    // Whether the run completed, with the log line that shows it did not
+    pub outcome: LogOutcome,
+    pub outcome_reason: Option<String>,
+    // Status per test binary, for runners that say which binary a result came from
+    pub binaries: std::collections::HashMap<String, BTreeMap<String, String>>,
+    // Documentation examples among the reported names, by name
+    pub doctests: std::collections::HashMap<String, Doctest>,
+    // Status of every try of tests the runner retried, and those that passed only after failing
+    pub retries: std::collections::HashMap<String, Vec<String>>,
+    pub flaky: std::collections::HashSet<String>,
+}
+
+/// Per-suite totals from the runner's summary lines. A nextest summary covers the whole run,
+/// so it replaces the libtest lines of the binaries it ran.
+pub(crate) fn summary_line_totals(content: &str) -> Vec<SuiteSummary> {
+    let mut suites = Vec::new();
+    for line in content.lines() {
+        let line = strip_ansi_color_codes(line);
+        if let Some(captures) = NEXTEST_SUMMARY_RE.captures(&line) {
+            let mut summary = SuiteSummary { exec_time: captures[1].parse().ok(), ..Default::default() };
+            for count in NEXTEST_SUMMARY_COUNT_RE.captures_iter(&captures[2]) {
+                let n: usize = count[1].parse().unwrap_or(0);
+                match &count[2] {
+                    "passed" => summary.passed += n,
+                    "skipped" => summary.ignored += n,
+                    _ => summary.failed += n,
+                }
+            }
+            return vec![summary];
+        }
+        if let Some(captures) = LIBTEST_SUMMARY_RE.captures(&line) {
+            suites.push(SuiteSummary {
+                passed: captures[1].parse().unwrap_or(0),
+                failed: captures[2].parse().unwrap_or(0),
+                ignored: captures[3].parse().unwrap_or(0),
+                exec_time: captures.get(4).and_then(|m| m.as_str().parse().ok()),
+                ..Default::default()
+            });
+        }
+    }
+    suites
+}
+
+// Sum of every suite a log reported, `None` if it printed no summary at all
+fn summary_totals(parsed: &ParsedLog) -> Option<SuiteSummary> {
+    if parsed.suites.is_empty() {
+        return None;
+    }
+    let mut totals = SuiteSummary::default();
+    for suite in &parsed.suites {
+        totals.passed += suite.passed;
+        totals.failed += suite.failed;
+        totals.ignored += suite.ignored;
+        if let Some(exec_time) = suite.exec_time {
+            totals.exec_time = Some(totals.exec_time.unwrap_or(0.0) + exec_time);
+        }
+    }
+    Some(totals)
+}
+
+// Results as the runner counts them: a test name reported by several binaries once per binary
+fn per_binary_counts(parsed: &ParsedLog) -> (usize, usize, usize) {
+    let mut counts = (0, 0, 0);
+    let mut count = |status: &str| match status {
+        "passed" => counts.0 += 1,
+        "failed" => counts.1 += 1,
+        _ => counts.2 += 1,
+    };
+    for test in &parsed.all {
+        match parsed.binaries.get(test) {
+            Some(per_binary) => per_binary.values().for_each(|status| count(status)),
+            None => {
+                if parsed.passed.contains(test) { count("passed"); }
+                if parsed.failed.contains(test) { count("failed"); }
+                if parsed.ignored.contains(test) { count("ignored"); }
+            }
+        }
+    }
+    counts
+}
+
+// A parser that silently misses lines leaves counts below what the runner itself reported
+fn parser_health_warning(label: &str, parsed: &ParsedLog) -> Option<String> {
+    let totals = summary_totals(parsed)?;
+    let found = per_binary_counts(parsed);
+    // Runners may leave out passing or skipped tests at their default verbosity (nextest SKIP
+    // lines, Surefire's passing methods), so those are compared only when the log lists some
+    let passed_match = found.0 == totals.passed || found.0 == 0;
+    let ignored_match = found.2 == totals.ignored || found.2 == 0;
+    if passed_match && found.1 == totals.failed && ignored_match {
+        return None;
+    }
+    Some(format!(
+        "{}.log: summary lines report {} passed, {} failed, {} ignored but the {} parser found {} passed, {} failed, {} ignored",
+        label, totals.passed, totals.failed, totals.ignored, parsed.parser, found.0, found.1, found.2
+    ))
+}
+
+/// Judges from the whole log whether the run completed, failed to build or was aborted
+pub(crate) fn detect_log_outcome(content: &str, parsed: &ParsedLog) -> (LogOutcome, Option<String>) {
+    let first_match = |re: &Regex| content.lines().enumerate()
+        .map(|(i, line)| (i, strip_ansi_color_codes(line)))
+        .find(|(_, line)| re.is_match(line.trim()) && !is_status_line(line))
+        .map(|(i, line)| format!("line {}: {}", i + 1, line.trim()));
+
+    // Surefire's default reporter only counts passing tests per class
+    let ran_tests = !parsed.all.is_empty() || parsed.suites.iter().any(|suite| suite.passed + suite.failed + suite.ignored > 0);
+    if !ran_tests {
+        if let Some(reason) = first_match(&COMPILE_ERROR_RE).or_else(|| first_match(&BUILD_ERROR_RE)) {
+            return (LogOutcome::BuildFailed, Some(reason));
+        }
+        let reason = first_match(&ABORT_RE).unwrap_or_else(|| "no test results in the log".to_string());
+        return (LogOutcome::Aborted, Some(reason));
+    }
+    match first_match(&ABORT_RE) {
+        Some(reason) => (LogOutcome::Aborted, Some(reason)),
+        None => (LogOutcome::Completed, None),
+    }
+}
+
+/// Totals from the end of one test binary's run
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SuiteSummary {
+    // Class the totals belong to, for runners that only list some of its tests (Surefire)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+    pub exec_time: Option<f64>,
 }
 
 // ---------------- Single-line (ANSI) aware parsing ----------------
-fn strip_ansi_color_codes(s: &str) -> String {
+pub(crate) fn strip_ansi_color_codes(s: &str) -> String {
     ANSI_RE.replace_all(s, "").into_owned()
 }
 
-fn parse_rust_log_single_line(text: &str) -> ParsedLog {
+pub(crate) fn parse_rust_log_single_line(text: &str, trace: bool) -> ParsedLog {
+    let mut recorder = TraceRecorder::new(trace, "ui_test");
     let mut passed = std::collections::HashSet::new();
     let mut failed = std::collections::HashSet::new();
     let mut ignored = std::collections::HashSet::new();
@@ -857,6 +1685,10 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
         if status == "failed" || status == "error" {
             status = "failed".to_string();
         }
+        if recorder.enabled() {
+            let (line_number, line) = line_at(&clean, cap.get(0).unwrap().start());
+            recorder.record("ENH_TEST_RE_1", &name, &status, Some(line_number), line);
+        }
         match status.as_str() {
             "ok" => { passed.insert(name); }
             "failed" => { failed.insert(name); }
@@ -872,6 +1704,10 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
         if status == "failed" || status == "error" {
             status = "failed".to_string();
         }
+        if recorder.enabled() {
+            let (line_number, line) = line_at(&clean, cap.get(0).unwrap().start());
+            recorder.record("TEST_MIXED_FORMAT_RE", &name, &status, Some(line_number), line);
+        }
         match status.as_str() {
             "ok" => { passed.insert(name); }
             "failed" => { failed.insert(name); }
@@ -881,13 +1717,14 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
     }
 
     // UI test format: "path/to/test.rs ... ok" (without "test" keyword)
-    for line in clean.lines() {
+    for (line_idx, line) in clean.lines().enumerate() {
         if let Some(cap) = UI_TEST_PATH_RE.captures(line) {
             let name = cap.get(1).unwrap().as_str().to_string();
             let mut status = cap.get(2).unwrap().as_str().to_lowercase();
             if status == "failed" || status == "error" {
                 status = "failed".to_string();
             }
+            recorder.record("UI_TEST_PATH_RE", &name, &status, Some(line_idx + 1), line);
             match status.as_str() {
                 "ok" => { passed.insert(name); }
                 "failed" => { failed.insert(name); }
@@ -898,13 +1735,14 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
     }
 
     // UI test format: "path/to/test.toml ... ok" (including .toml files)
-    for line in clean.lines() {
+    for (line_idx, line) in clean.lines().enumerate() {
         if let Some(cap) = UI_TEST_PATH_SIMPLE_RE.captures(line) {
             let name = cap.get(1).unwrap().as_str().to_string();
             let mut status = cap.get(2).unwrap().as_str().to_lowercase();
             if status == "failed" || status == "error" {
                 status = "failed".to_string();
             }
+            recorder.record("UI_TEST_PATH_SIMPLE_RE", &name, &status, Some(line_idx + 1), line);
             match status.as_str() {
                 "ok" => { passed.insert(name); }
                 "failed" => { failed.insert(name); }
@@ -967,6 +1805,10 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
                 context.contains("thread") ||
                 context.contains("kind:")
             ) {
+                if recorder.enabled() {
+                    let (line_number, line) = line_at(&clean, search_pos + match_start);
+                    recorder.note("STATUS_IN_TEXT_RE", &name, &status, Some(line_number), line, "error inside diagnostic output, skipped");
+                }
                 continue;
             }
             
@@ -992,7 +1834,10 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
                                             window.to_lowercase().contains("panicked at");
                     
                     if panic_for_this_test {
+                        recorder.note("SINGLE_LINE_STATUS_AT_START_RE", &name, &status, None, line, "status mixed with logging output, accepted because of panic evidence");
                         status_matches.push((status, 0)); // Use 0 as position indicator for start-of-line matches
+                    } else {
+                        recorder.note("SINGLE_LINE_STATUS_AT_START_RE", &name, &status, None, line, "status mixed with logging output without panic evidence, skipped");
                     }
                 } else {
                     status_matches.push((status, 0));
@@ -1002,6 +1847,11 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
         
         // Use the last (most recent) valid status match
         if let Some((status, _)) = status_matches.last() {
+            if recorder.enabled() {
+                let (line_number, line) = line_at(&clean, cap.get(0).unwrap().start());
+                let note = format!("last of {} status candidate(s) before the next test", status_matches.len());
+                recorder.note("SINGLE_LINE_START_RE", &name, status, Some(line_number), line, &note);
+            }
             match status.as_str() {
                 "ok" => { passed.insert(name); }
                 "failed" | "error" => { failed.insert(name); }
@@ -1016,7 +1866,7 @@ fn parse_rust_log_single_line(text: &str) -> ParsedLog {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    ParsedLog { passed, failed, ignored, all, trace: recorder.into_events(), ..Default::default() }
 }
 
 // Helper function to check if an error status is part of diagnostic messages
@@ -1079,69 +1929,128 @@ fn process_test_status(
     }
 }
 
-fn looks_single_line_like(text: &str) -> bool {
-    let line_count = text.lines().count();
-    let has_ansi = ANSI_RE.is_match(text);
-    let test_count = SIMPLE_PATTERN_RE.find_iter(text).count();
-    
-    // Count UI test patterns line-by-line since they use line anchors
-    let mut ui_test_count = 0;
-    for line in text.lines() {
-        if UI_TEST_PATH_RE.is_match(line) || UI_TEST_PATH_SIMPLE_RE.is_match(line) {
-            ui_test_count += 1;
-        }
+// ---------------- Format detection ----------------
+// Confidence scores in 0.0..=1.0; `parsers::ParserRegistry` runs the parser with the highest one
+
+fn count_matching_lines(text: &str, patterns: &[&Regex]) -> usize {
+    text.lines().filter(|line| patterns.iter().any(|re| re.is_match(line))).count()
+}
+
+pub(crate) fn nextest_confidence(text: &str) -> f32 {
+    let nextest_lines = count_matching_lines(text, &[&NEXTEST_PASS_RE, &NEXTEST_FAIL_RE, &NEXTEST_SKIP_RE, &NEXTEST_TIMEOUT_RE]);
+    let libtest_lines = count_matching_lines(text, &[&TEST_LINE_RE, &TEST_MIXED_FORMAT_RE]);
+    let lower = text.to_lowercase();
+    let has_banner = ["nextest run id", "starting tests across", "cargo nextest run"].iter().any(|marker| lower.contains(marker))
+        || text.lines().any(|line| NEXTEST_START_RE.is_match(line));
+
+    // A mention of nextest without any nextest result line is weak evidence (e.g. a setup script)
+    if nextest_lines == 0 {
+        return if has_banner { 0.3 } else { 0.0 };
+    }
+    // Any nextest result line wins over libtest: this parser reads the libtest lines around it,
+    // the libtest parser skips nextest's, whatever their share of the log
+    let share = nextest_lines as f32 / (nextest_lines + libtest_lines) as f32;
+    0.9 + 0.05 * share + if has_banner { 0.05 } else { 0.0 }
+}
+
+pub(crate) fn libtest_confidence(text: &str) -> f32 {
+    let libtest_lines = count_matching_lines(text, &[&TEST_LINE_RE, &TEST_MIXED_FORMAT_RE]);
+    let nextest_lines = count_matching_lines(text, &[&NEXTEST_PASS_RE, &NEXTEST_FAIL_RE, &NEXTEST_SKIP_RE, &NEXTEST_TIMEOUT_RE]);
+    let ui_lines = count_matching_lines(text, &[&UI_TEST_PATH_RE, &UI_TEST_PATH_SIMPLE_RE]);
+    let total = libtest_lines + nextest_lines + ui_lines;
+
+    // The multi-pass parser is the generic fallback, so it never scores zero
+    if total == 0 {
+        return 0.2;
     }
-    
-    // Check if it looks like a UI test format (many path-based test results)
-    let has_ui_tests = ui_test_count > 10;
-    
-    (line_count <= 3 && test_count > 5) || has_ansi || has_ui_tests
+    0.2 + 0.7 * libtest_lines as f32 / total as f32
 }
 
-fn looks_nextest_format(text: &str) -> bool {
-    // Check for nextest-specific patterns
-    let nextest_indicators = [
-        "Nextest run ID",
-        "nextest run",
-        "Starting tests across",
-        "PASS [",
-        "FAIL [",
-        "START             ", // Added START pattern from your example
-    ];
-    
-    let has_indicators = nextest_indicators.iter().any(|indicator| 
-        text.to_lowercase().contains(&indicator.to_lowercase())
-    );
-    
-    // Count nextest-style result lines
-    let nextest_lines = NEXTEST_PASS_RE.find_iter(text).count() + 
-                       NEXTEST_FAIL_RE.find_iter(text).count() + 
-                       NEXTEST_SKIP_RE.find_iter(text).count();
-    
-    // Also check for the mixed format pattern with traditional + nextest
-    let has_mixed_format = text.contains("PASS [") && text.contains("test ") && text.contains("... ok");
-    
-    // Check for cargo nextest run command line
-    let has_nextest_command = text.contains("cargo nextest run");
-    
-    has_indicators || nextest_lines > 5 || has_mixed_format || has_nextest_command
+pub(crate) fn ui_test_confidence(text: &str) -> f32 {
+    let clean = strip_ansi_color_codes(text);
+    let test_count = SIMPLE_PATTERN_RE.find_iter(&clean).count();
+
+    // Whole runs collapsed onto a few lines can only be read by the single-line parser
+    if text.lines().count() <= 3 && test_count > 5 {
+        return 0.95;
+    }
+
+    let mut score: f32 = 0.0;
+    // Colour codes around the status hide results from the line based parsers
+    if ANSI_RE.is_match(text) && test_count > 0 {
+        let visible = count_matching_lines(text, &[&TEST_LINE_RE]);
+        let hidden = test_count.saturating_sub(visible);
+        score = 0.6 + 0.3 * hidden as f32 / test_count as f32;
+    }
+
+    let ui_lines = count_matching_lines(&clean, &[&UI_TEST_PATH_RE, &UI_TEST_PATH_SIMPLE_RE]);
+    if ui_lines > 0 {
+        let libtest_lines = count_matching_lines(&clean, &[&TEST_LINE_RE]);
+        score = score.max(0.2 + 0.75 * ui_lines as f32 / (ui_lines + libtest_lines) as f32);
+    }
+    score
 }
 
-fn parse_nextest_log(text: &str) -> ParsedLog {
+pub(crate) fn parse_nextest_log(text: &str, trace: bool) -> ParsedLog {
+    let mut recorder = TraceRecorder::new(trace, "nextest");
     let mut passed = std::collections::HashSet::new();
     let mut failed = std::collections::HashSet::new();
     let mut ignored = std::collections::HashSet::new();
+    let mut binaries = std::collections::HashMap::new();
+    // Keyed by (binary, test): the same test name in another binary is another test.
+    // Per retried test: the status of each try, by try number
+    let mut tries: BTreeMap<(Option<String>, String), BTreeMap<u32, &str>> = BTreeMap::new();
+    let mut durations: std::collections::HashMap<(Option<String>, String), f64> = std::collections::HashMap::new();
 
     let lines: Vec<&str> = text.lines().collect();
 
     // Parse nextest format using separate regex patterns for better accuracy
     for (i, line) in lines.iter().enumerate() {
+        // Retried tests, before the PASS/FAIL patterns that would also match these lines
+        if let Some(captures) = NEXTEST_TRY_RE.captures(line) {
+            let (binary, test_name) = split_nextest_id(&captures[3]);
+            let status = if &captures[2] == "PASS" { "passed" } else { "failed" };
+            recorder.record("NEXTEST_TRY_RE", &test_name, status, Some(i + 1), line);
+            if let Some(seconds) = nextest_seconds(line) {
+                durations.insert((binary.map(str::to_string), test_name.clone()), seconds);
+            }
+            tries.entry((binary.map(str::to_string), test_name)).or_default()
+                .insert(captures[1].parse().unwrap_or(1), status);
+            continue;
+        }
+        if let Some(captures) = NEXTEST_RETRY_RE.captures(line) {
+            let (_, test_name) = split_nextest_id(&captures[1]);
+            recorder.note("NEXTEST_RETRY_RE", &test_name, "retry", Some(i + 1), line, "test is being retried");
+            continue;
+        }
+        if let Some(captures) = NEXTEST_FLAKY_RE.captures(line) {
+            let (binary, test_name) = split_nextest_id(&captures[2]);
+            recorder.record("NEXTEST_FLAKY_RE", &test_name, "passed", Some(i + 1), line);
+            if let Some(seconds) = nextest_seconds(line) {
+                durations.insert((binary.map(str::to_string), test_name.clone()), seconds);
+            }
+            // The summary line alone says which try passed; the ones before it failed
+            let passed_on: u32 = captures[1].parse().unwrap_or(1);
+            let attempts = tries.entry((binary.map(str::to_string), test_name)).or_default();
+            for attempt in 1..passed_on {
+                attempts.entry(attempt).or_insert("failed");
+            }
+            attempts.insert(passed_on, "passed");
+            continue;
+        }
+
         // Parse PASS lines
         if let Some(captures) = NEXTEST_PASS_RE.captures(line) {
             let full_match = captures.get(1).unwrap().as_str().trim();
-            // Extract just the test name part (after the crate name)
-            let test_name = extract_test_name_from_nextest_line(full_match);
+            let (binary, test_name) = split_nextest_id(full_match);
             println!("NEXTEST PASS: '{}' -> '{}'", full_match, test_name);
+            recorder.record("NEXTEST_PASS_RE", &test_name, "ok", Some(i + 1), line);
+            if let Some(seconds) = nextest_seconds(line) {
+                durations.insert((binary.map(str::to_string), test_name.clone()), seconds);
+            }
+            if let Some(binary) = binary {
+                record_binary_status(&mut binaries, &test_name, binary, "passed");
+            }
             passed.insert(test_name);
             continue;
         }
@@ -1149,19 +2058,53 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
         // Parse FAIL lines
         if let Some(captures) = NEXTEST_FAIL_RE.captures(line) {
             let full_match = captures.get(1).unwrap().as_str().trim();
-            // Extract just the test name part (after the crate name)
-            let test_name = extract_test_name_from_nextest_line(full_match);
+            let (binary, test_name) = split_nextest_id(full_match);
             println!("NEXTEST FAIL: '{}' -> '{}'", full_match, test_name);
+            recorder.record("NEXTEST_FAIL_RE", &test_name, "failed", Some(i + 1), line);
+            if let Some(seconds) = nextest_seconds(line) {
+                durations.insert((binary.map(str::to_string), test_name.clone()), seconds);
+            }
+            if let Some(binary) = binary {
+                record_binary_status(&mut binaries, &test_name, binary, "failed");
+            }
             failed.insert(test_name);
             continue;
         }
         
+        // A timed out test failed; its line carries how long it ran before nextest killed it
+        if let Some(captures) = NEXTEST_TIMEOUT_RE.captures(line) {
+            let (binary, test_name) = split_nextest_id(&captures[1]);
+            recorder.record("NEXTEST_TIMEOUT_RE", &test_name, "failed", Some(i + 1), line);
+            if let Some(seconds) = nextest_seconds(line) {
+                durations.insert((binary.map(str::to_string), test_name.clone()), seconds);
+            }
+            if let Some(binary) = binary {
+                record_binary_status(&mut binaries, &test_name, binary, "failed");
+            }
+            failed.insert(test_name);
+            continue;
+        }
+
+        // A test still running past the slow timeout; the time grows with each line until its result
+        if let Some(captures) = NEXTEST_SLOW_RE.captures(line) {
+            let (binary, test_name) = split_nextest_id(&captures[1]);
+            recorder.note("NEXTEST_SLOW_RE", &test_name, "slow", Some(i + 1), line, "test is still running");
+            if let Some(seconds) = nextest_seconds(line) {
+                durations.insert((binary.map(str::to_string), test_name), seconds);
+            }
+            continue;
+        }
+
         // Parse SKIP/IGNORED lines - note: using capture group 2 for SKIP/IGNORED
         if let Some(captures) = NEXTEST_SKIP_RE.captures(line) {
             // For SKIP/IGNORED pattern, the test name is in group 2
             if let Some(test_name_match) = captures.get(2) {
                 let full_match = test_name_match.as_str().trim();
-                let test_name = extract_test_name_from_nextest_line(full_match);
+                let (binary, test_name) = split_nextest_id(full_match);
+                recorder.record("NEXTEST_SKIP_RE", &test_name, "ignored", Some(i + 1), line);
+                if let Some(binary) = binary {
+                    record_binary_status(&mut binaries, &test_name, binary, "ignored");
+                }
                 ignored.insert(test_name);
             }
             continue;
@@ -1171,6 +2114,7 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
         if let Some(captures) = TEST_LINE_RE.captures(line) {
             let test_name = captures.get(1).unwrap().as_str().to_string();
             let status = captures.get(2).unwrap().as_str().to_lowercase();
+            recorder.record("TEST_LINE_RE", &test_name, &status, Some(i + 1), line);
             
             match status.as_str() {
                 "ok" => { passed.insert(test_name); }
@@ -1185,6 +2129,7 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
         if let Some(captures) = TEST_MIXED_FORMAT_RE.captures(line) {
             let test_name = captures.get(1).unwrap().as_str().to_string();
             let status = captures.get(2).unwrap().as_str().to_lowercase();
+            recorder.record("TEST_MIXED_FORMAT_RE", &test_name, &status, Some(i + 1), line);
             
             match status.as_str() {
                 "ok" => { passed.insert(test_name); }
@@ -1202,6 +2147,7 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
             if status == "failed" || status == "error" {
                 status = "failed".to_string();
             }
+            recorder.record("ENH_TEST_RE_1", &test_name, &status, Some(i + 1), line);
             match status.as_str() {
                 "ok" => { passed.insert(test_name); }
                 "failed" => { failed.insert(test_name); }
@@ -1233,6 +2179,9 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
                     }
                     
                     // Look for standalone status words
+                    if ["ok", "failed", "error", "ignored"].iter().any(|s| search_line.eq_ignore_ascii_case(s)) {
+                        recorder.note("TEST_START_RE", &test_name, search_line, Some(j + 1), lines[j], "standalone status after diagnostic output");
+                    }
                     if search_line.eq_ignore_ascii_case("ok") {
                         passed.insert(test_name.clone());
                         break;
@@ -1249,27 +2198,81 @@ fn parse_nextest_log(text: &str) -> ParsedLog {
         }
     }
 
+    // A retried test ends with the status of its last try; failed tries before a pass make it
+    // flaky. With a binary id only that binary's result changes, and the test's overall status
+    // is merged again from every binary that reported it.
+    let mut retries: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    let mut flaky = std::collections::HashSet::new();
+    for ((binary, test_name), attempts) in tries {
+        let Some(&last) = attempts.values().next_back() else { continue };
+        if last == "passed" && attempts.values().any(|status| *status == "failed") {
+            flaky.insert(test_name.clone());
+        }
+        // Several binaries retrying the same name share one list, in binary order
+        retries.entry(test_name.clone()).or_default().extend(attempts.into_values().map(str::to_string));
+        let merged = match binary {
+            Some(binary) => {
+                let per_binary = binaries.entry(test_name.clone()).or_default();
+                per_binary.insert(binary, last.to_string());
+                let any = |status: &str| per_binary.values().any(|recorded| recorded == status);
+                if any("failed") { "failed" } else if any("passed") { "passed" } else { "ignored" }
+            }
+            None => last,
+        };
+        passed.remove(&test_name);
+        failed.remove(&test_name);
+        ignored.remove(&test_name);
+        match merged {
+            "passed" => passed.insert(test_name),
+            "failed" => failed.insert(test_name),
+            _ => ignored.insert(test_name),
+        };
+    }
+    // Per test name, its longest run in any binary
+    let mut longest: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+    for ((_, test_name), seconds) in durations {
+        let entry = longest.entry(test_name).or_insert(seconds);
+        *entry = entry.max(seconds);
+    }
+
     let mut all = std::collections::HashSet::new();
     all.extend(passed.iter().cloned());
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
 
-    ParsedLog { passed, failed, ignored, all }
+    let failures = collect_failure_details(text, &failed);
+    ParsedLog {
+        passed,
+        failed,
+        ignored,
+        all,
+        failures,
+        binaries,
+        retries,
+        flaky,
+        durations: longest,
+        trace: recorder.into_events(),
+        ..Default::default()
+    }
 }
 
-fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
+fn parse_log_file(file_path: &str, trace: bool, parser_override: Option<&String>) -> Result<ParsedLog, String> {
     let content = fs::read_to_string(file_path)
         .map_err(|e| format!("Failed to read log file {}: {}", file_path, e))?;
 
-    // Check for nextest format first
-    if looks_nextest_format(&content) {
-        return Ok(parse_nextest_log(&content));
-    }
+    let registry = ParserRegistry::with_default_parsers();
+    let parsed = match parser_override {
+        Some(name) => registry.parse_as(name, &content, trace)?,
+        None => registry.parse(&content, trace),
+    };
+    let picked = if parsed.overridden { "override" } else { "detected" };
+    println!("Parsed {} with {} parser ({}, confidence {:.2})", file_path, parsed.parser, picked, parsed.confidence);
+    Ok(parsed)
+}
 
-    // Switch to ANSI/single-line parser when appropriate
-    if looks_single_line_like(&content) {
-        return Ok(parse_rust_log_single_line(&content));
-    }
+// Multi-pass parser for plain `cargo test` output
+pub(crate) fn parse_libtest_log(content: &str, trace: bool) -> ParsedLog {
+    let mut recorder = TraceRecorder::new(trace, "libtest");
 
     let mut passed = std::collections::HashSet::new();
     let mut failed = std::collections::HashSet::new();
@@ -1279,11 +2282,12 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
     let lines: Vec<&str> = content.lines().collect();
     
     // First pass: handle normal test lines with immediate results
-    for line in &lines {
+    for (i, line) in lines.iter().enumerate() {
         // Handle standard format: "test name ... status"
         if let Some(captures) = TEST_LINE_RE.captures(line) {
             let test_name = captures.get(1).unwrap().as_str().to_string();
             let status = captures.get(2).unwrap().as_str().to_lowercase();
+            recorder.record("TEST_LINE_RE", &test_name, &status, Some(i + 1), line);
             
             *freq.entry(test_name.clone()).or_insert(0) += 1;
             
@@ -1300,6 +2304,7 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
         if let Some(captures) = TEST_MIXED_FORMAT_RE.captures(line) {
             let test_name = captures.get(1).unwrap().as_str().to_string();
             let status = captures.get(2).unwrap().as_str().to_lowercase();
+            recorder.record("TEST_MIXED_FORMAT_RE", &test_name, &status, Some(i + 1), line);
             
             *freq.entry(test_name.clone()).or_insert(0) += 1;
             
@@ -1360,6 +2365,7 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                 || stripped.eq_ignore_ascii_case("error")
             {
                 let status = stripped.to_lowercase();
+                recorder.note("STANDALONE_STATUS", &test_name, &status, Some(j + 1), line, "standalone status line after the test start");
                 *freq.entry(test_name.clone()).or_insert(0) += 1;
 
                 match status.as_str() {
@@ -1374,10 +2380,12 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
 
             // Check for status words at the end of lines (after debug output) OR at the beginning mixed with logging
             let mut status_match = None;
+            let mut status_pattern = "STATUS_AT_END_RE";
             if let Some(captures) = STATUS_AT_END_RE.captures(line) {
                 status_match = Some(captures);
             } else if let Some(captures) = STATUS_AT_START_RE.captures(line) {
                 status_match = Some(captures);
+                status_pattern = "STATUS_AT_START_RE";
             }
 
             if let Some(captures) = status_match {
@@ -1385,11 +2393,13 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                 
                 // Enhanced filtering to avoid false positives from diagnostic messages
                 if is_diagnostic_error(&status, line) {
+                    recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "error inside diagnostic output, skipped");
                     continue;
                 }
                 
                 // Also skip if the status word appears in the middle of a diagnostic message
                 if is_status_in_diagnostic_context(&status, line) {
+                    recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "status inside a diagnostic message, skipped");
                     continue;
                 }
 
@@ -1409,8 +2419,12 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                     
                     if !has_panic_evidence(&test_name, &lines, search_start, search_end) {
                         // This status is mixed with logging output and no panic evidence, skip it
+                        recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "status mixed with logging output without panic evidence, skipped");
                         continue;
                     }
+                    recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "status mixed with logging output, accepted because of panic evidence");
+                } else {
+                    recorder.record(status_pattern, &test_name, &status, Some(j + 1), line);
                 }
                 
                 process_test_status(&status, &test_name, &mut passed, &mut failed, &mut ignored, &mut freq);
@@ -1435,16 +2449,19 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                     || stripped.eq_ignore_ascii_case("error")
                 {
                     let status = stripped.to_lowercase();
+                    recorder.note("STANDALONE_STATUS", &test_name, &status, Some(j + 1), line, "standalone status line in the extended scan window");
                     process_test_status(&status, &test_name, &mut passed, &mut failed, &mut ignored, &mut freq);
                     break;
                 }
 
                 // Check for status words at the end of lines (after debug output) OR at the beginning mixed with logging
                 let mut status_match = None;
+                let mut status_pattern = "STATUS_AT_END_RE";
                 if let Some(captures) = STATUS_AT_END_RE.captures(line) {
                     status_match = Some(captures);
                 } else if let Some(captures) = STATUS_AT_START_RE.captures(line) {
                     status_match = Some(captures);
+                    status_pattern = "STATUS_AT_START_RE";
                 }
 
                 if let Some(captures) = status_match {
@@ -1452,11 +2469,13 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                     
                     // Enhanced filtering to avoid false positives from diagnostic messages
                     if is_diagnostic_error(&status, line) {
+                        recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "error inside diagnostic output, skipped");
                         continue;
                     }
                     
                     // Also skip if the status word appears in the middle of a diagnostic message
                     if is_status_in_diagnostic_context(&status, line) {
+                        recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "status inside a diagnostic message, skipped");
                         continue;
                     }
 
@@ -1476,8 +2495,12 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                         
                         if !has_panic_evidence(&test_name, &lines, search_start, search_end) {
                             // This status is mixed with logging output and no panic evidence, skip it
+                            recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "status mixed with logging output without panic evidence, skipped");
                             continue;
                         }
+                        recorder.note(status_pattern, &test_name, &status, Some(j + 1), line, "status mixed with logging output, accepted because of panic evidence");
+                    } else {
+                        recorder.record(status_pattern, &test_name, &status, Some(j + 1), line);
                     }
                     
                     process_test_status(&status, &test_name, &mut passed, &mut failed, &mut ignored, &mut freq);
@@ -1498,6 +2521,7 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                 if let Some(captures) = TEST_WITH_O_RE.captures(lines[j]) {
                     let test_name = captures.get(1).unwrap().as_str().to_string();
                     if !passed.contains(&test_name) && !failed.contains(&test_name) && !ignored.contains(&test_name) {
+                        recorder.note("TEST_WITH_O_RE", &test_name, "ok", Some(j + 1), lines[j], "status split across lines as \"o\" / \"k\"");
                         *freq.entry(test_name.clone()).or_insert(0) += 1;
                         passed.insert(test_name);
                     }
@@ -1511,6 +2535,7 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
             let test_name = captures.get(1).unwrap().as_str().to_string();
             if i + 1 < lines.len() && lines[i + 1].trim() == "k" {
                 if !passed.contains(&test_name) && !failed.contains(&test_name) && !ignored.contains(&test_name) {
+                    recorder.note("TEST_WITH_O_RE", &test_name, "ok", Some(i + 1), line, "status split across lines as \"... o\" / \"k\"");
                     *freq.entry(test_name.clone()).or_insert(0) += 1;
                     passed.insert(test_name);
                 }
@@ -1591,6 +2616,8 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
         
         // Use the last (most recent) valid status match
         if let Some((status, _)) = status_matches.last() {
+            let note = format!("last of {} status candidate(s) within 100 lines of the test start", status_matches.len());
+            recorder.note("TEST_STARTS_RE", &test_name, status, Some(line_idx + 1), lines[line_idx], &note);
             process_test_status(&status, &test_name, &mut passed, &mut failed, &mut ignored, &mut freq);
         }
     }
@@ -1623,6 +2650,9 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
                     }
                     
                     // Look for standalone status words
+                    if ["ok", "failed", "error", "ignored"].iter().any(|s| search_line.eq_ignore_ascii_case(s)) {
+                        recorder.note("TEST_START_RE", &test_name, search_line, Some(j + 1), lines[j], "standalone status after diagnostic output");
+                    }
                     if search_line.eq_ignore_ascii_case("ok") {
                         passed.insert(test_name.clone());
                         found_status = true;
@@ -1648,7 +2678,7 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
 
     // Also read the "failures:" block to catch names not emitted on one-line form
     let mut collecting = false;
-    for line in &lines {
+    for (i, line) in lines.iter().enumerate() {
         let trimmed = line.trim();
         if trimmed == "failures:" {
             collecting = true;
@@ -1662,6 +2692,7 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
             if let Some(captures) = FAILURES_BLOCK_RE.captures(line) {
                 let test_name = captures.get(1).unwrap().as_str().to_string();
                 if !test_name.starts_with("----") {
+                    recorder.note("FAILURES_BLOCK_RE", &test_name, "failed", Some(i + 1), line, "listed in the failures: block");
                     failed.insert(test_name);
                 }
                 continue;
@@ -1678,12 +2709,223 @@ fn parse_rust_log_file(file_path: &str) -> Result<ParsedLog, String> {
     all.extend(failed.iter().cloned());
     all.extend(ignored.iter().cloned());
     
-    Ok(ParsedLog {
+    let failures = collect_failure_details(content, &failed);
+    ParsedLog {
         passed,
         failed,
         ignored,
         all,
-    })
+        failures,
+        binaries: libtest_binary_statuses(content),
+        durations: libtest_report_times(content),
+        trace: recorder.into_events(),
+        ..Default::default()
+    }
+}
+
+// ---------------- Test binaries ----------------
+
+/// Name of a test binary without its directory and hash: `target/debug/deps/foo_bar-1a2b3c4d5e6f7a8b`
+/// becomes `foo_bar`
+fn binary_name(path: &str) -> String {
+    let file = path.trim_matches('`').rsplit(['/', '\\']).next().unwrap_or(path);
+    let file = file.strip_suffix(".exe").unwrap_or(file);
+    BINARY_HASH_RE.replace(file, "").to_string()
+}
+
+/// Records the status one binary reported for a test; a failure wins within the same binary
+pub(crate) fn record_binary_status(
+    binaries: &mut std::collections::HashMap<String, BTreeMap<String, String>>,
+    test: &str,
+    binary: &str,
+    status: &str,
+) {
+    let entry = binaries.entry(test.to_string()).or_default()
+        .entry(binary.to_string())
+        .or_insert_with(|| status.to_string());
+    if status == "failed" {
+        *entry = status.to_string();
+    }
+}
+
+/// Per-test times of a `cargo test -- -Zunstable-options --report-time` log
+fn libtest_report_times(content: &str) -> std::collections::HashMap<String, f64> {
+    content.lines()
+        .filter_map(|line| LIBTEST_REPORT_TIME_RE.captures(line))
+        .filter_map(|captures| Some((captures[1].to_string(), captures[2].parse().ok()?)))
+        .collect()
+}
+
+/// Per-binary statuses of a `cargo test` log, attributing each result line to the preceding
+/// `Running …` or `Doc-tests …` header
+fn libtest_binary_statuses(content: &str) -> std::collections::HashMap<String, BTreeMap<String, String>> {
+    let mut binaries = std::collections::HashMap::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        let line = strip_ansi_color_codes(line);
+        if let Some(captures) = RUNNING_BINARY_RE.captures(&line) {
+            current = Some(binary_name(&captures[1]));
+            continue;
+        }
+        if let Some(captures) = DOC_TESTS_RE.captures(&line) {
+            current = Some(format!("{} (doctests)", &captures[1]));
+            continue;
+        }
+        let Some(binary) = &current else { continue };
+        let Some(captures) = TEST_LINE_RE.captures(&line).or_else(|| TEST_MIXED_FORMAT_RE.captures(&line)) else { continue };
+        let status = match captures[2].to_lowercase().as_str() {
+            "ok" => "passed",
+            "ignored" => "ignored",
+            _ => "failed",
+        };
+        record_binary_status(&mut binaries, &captures[1], binary, status);
+    }
+    binaries
+}
+
+// ---------------- Failure details ----------------
+
+/// Panic site, message and captured output of every failed test that has them in the log
+pub(crate) fn collect_failure_details(
+    content: &str,
+    failed: &std::collections::HashSet<String>,
+) -> std::collections::HashMap<String, FailureDetail> {
+    let lines: Vec<&str> = content.lines().collect();
+    let blocks = captured_output_blocks(&lines, failed);
+    let panics = panic_sites(&lines);
+    let compile_failed = lines.iter().any(|line| COMPILE_ERROR_RE.is_match(line));
+
+    failed.iter().map(|name| {
+        let (location, message) = panics.get(name).cloned().unzip();
+        let mut detail = FailureDetail {
+            kind: FailureKind::Unknown,
+            location,
+            message: message.filter(|message| !message.is_empty()),
+            stdout: blocks.get(name).map(|output| output_excerpt(output)).unwrap_or_default(),
+        };
+        detail.kind = classify_failure(name, &detail, &lines, compile_failed);
+        (name.clone(), detail)
+    }).collect()
+}
+
+/// Failure detail from output captured for a single test, e.g. the `stdout` of a libtest JSON event
+pub(crate) fn failure_detail_from_output(name: &str, output: &str) -> FailureDetail {
+    let lines: Vec<&str> = output.lines().collect();
+    let (location, message) = panic_sites(&lines).remove(name).unzip();
+    let mut detail = FailureDetail {
+        kind: FailureKind::Unknown,
+        location,
+        message: message.filter(|message| !message.is_empty()),
+        stdout: output_excerpt(&lines),
+    };
+    detail.kind = classify_failure(name, &detail, &lines, false);
+    detail
+}
+
+// The panic message is the strongest signal; without one, fall back to what the log says around the test
+fn classify_failure(test_name: &str, detail: &FailureDetail, lines: &[&str], compile_failed: bool) -> FailureKind {
+    if let Some(message) = &detail.message {
+        return classify_panic_message(message);
+    }
+    let timed_out = lines.iter().any(|line| {
+        (line.contains("TIMEOUT [") || line.contains("has been running for over")) && line_names_test(line, test_name)
+    });
+    if timed_out {
+        FailureKind::Timeout
+    } else if has_panic_evidence(test_name, lines, 0, lines.len()) {
+        FailureKind::Panic
+    } else if compile_failed {
+        FailureKind::CompileError
+    } else if lines.iter().any(|line| is_diagnostic_error("error", line) && line_names_test(line, test_name)) {
+        // "test foo ... error" with diagnostic text: the harness, not the test, reported the error
+        FailureKind::Infrastructure
+    } else {
+        FailureKind::Unknown
+    }
+}
+
+fn classify_panic_message(message: &str) -> FailureKind {
+    let lower = message.to_lowercase();
+    if lower.contains("assertion") || lower.contains("left == right") || lower.contains("left != right") {
+        FailureKind::Assertion
+    } else if lower.contains("timed out") || lower.contains("timeout") || lower.contains("deadline has elapsed") {
+        FailureKind::Timeout
+    } else if ["fixture not found", "missing fixture", "failed to spawn", "address already in use", "connection refused",
+               "no space left on device", "too many open files"].iter().any(|marker| lower.contains(marker)) {
+        // Harness and environment messages only: an I/O error the test itself unwraps is its own failure
+        FailureKind::Infrastructure
+    } else {
+        FailureKind::Panic
+    }
+}
+
+// Output blocks of failed tests, up to the next block or the end of the failures section
+fn captured_output_blocks<'a>(
+    lines: &[&'a str],
+    failed: &std::collections::HashSet<String>,
+) -> std::collections::HashMap<String, Vec<&'a str>> {
+    let mut blocks: std::collections::HashMap<String, Vec<&'a str>> = std::collections::HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in lines {
+        if let Some(captures) = OUTPUT_BLOCK_RE.captures(line) {
+            let printed = captures.get(1).or_else(|| captures.get(2)).unwrap().as_str();
+            // nextest names the block "<binary id> <test>"; libtest only the test
+            let name = if failed.contains(printed) {
+                printed
+            } else {
+                printed.rsplit(' ').next().unwrap_or(printed)
+            };
+            current = failed.contains(name).then(|| name.to_string());
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed == "failures:" || trimmed.starts_with("test result:")
+            || NEXTEST_PASS_RE.is_match(line) || NEXTEST_FAIL_RE.is_match(line) || NEXTEST_START_RE.is_match(line) {
+            current = None;
+            continue;
+        }
+        if let Some(name) = &current {
+            blocks.entry(name.clone()).or_default().push(line);
+        }
+    }
+    blocks
+}
+
+// First panic per thread; libtest names each test's thread after the test
+fn panic_sites(lines: &[&str]) -> std::collections::HashMap<String, (String, String)> {
+    let mut panics = std::collections::HashMap::new();
+    for (i, line) in lines.iter().enumerate() {
+        let (thread, location, message) = if let Some(captures) = PANIC_OLD_RE.captures(line) {
+            (captures[1].to_string(), captures[3].to_string(), captures[2].to_string())
+        } else if let Some(captures) = PANIC_RE.captures(line) {
+            let inline = captures[3].trim();
+            let message = if inline.is_empty() {
+                // The message runs until a blank line or the backtrace note
+                lines[i + 1..].iter()
+                    .take_while(|next| !next.trim().is_empty() && !next.starts_with("note:") && !next.starts_with("stack backtrace:"))
+                    .map(|next| next.trim_end())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            } else {
+                inline.to_string()
+            };
+            (captures[1].to_string(), captures[2].to_string(), message)
+        } else {
+            continue;
+        };
+        panics.entry(thread).or_insert((location, message));
+    }
+    panics
+}
+
+fn output_excerpt(lines: &[&str]) -> String {
+    let lines: Vec<&str> = lines.iter()
+        .map(|line| line.trim_end())
+        .skip_while(|line| line.is_empty())
+        .collect();
+    let end = lines.iter().rposition(|line| !line.is_empty()).map(|i| i + 1).unwrap_or(0);
+    lines[end.saturating_sub(FAILURE_OUTPUT_LINES)..end].join("\n")
 }
 
 // ---------------- Duplicate detection (C5) parity----------------
@@ -1769,7 +3011,8 @@ fn is_true_duplicate(occ: &[Occur]) -> bool {
     false
 }
 
-fn detect_same_file_duplicates(raw_content: &str) -> Vec<String> {
+// Returns (test name, description) for every true duplicate
+pub(crate) fn detect_same_file_duplicates(raw_content: &str) -> Vec<(String, String)> {
     if raw_content.is_empty() { return vec![]; }
     let lines: Vec<&str> = raw_content.split('\n').collect();
     let mut current_file = "unknown".to_string();
@@ -1796,15 +3039,25 @@ fn detect_same_file_duplicates(raw_content: &str) -> Vec<String> {
         for (name, list) in by_name {
             if list.len() > 1 && is_true_duplicate(&list) {
                 let places: Vec<String> = list.iter().map(|o| format!("line {}", o.line_no)).collect();
-                out.push(format!("{} (appears {} times in {}: {})", name, places.len(), file, places.join(", ")));
+                let description = format!("{} (appears {} times in {}: {})", name, places.len(), file, places.join(", "));
+                out.push((name, description));
             }
         }
     }
     out
 }
 
-fn status_lookup(names: &[String], parsed: &ParsedLog) -> std::collections::HashMap<String, String> {
+fn status_lookup(
+    names: &[String],
+    label: &str,
+    parsed: &ParsedLog,
+    mapper: &NameMapper,
+) -> (std::collections::HashMap<String, String>, std::collections::HashMap<String, String>, Vec<NameResolution>) {
     let mut out = std::collections::HashMap::new();
+    // main.json names the log reports under another name, so per-test data is looked up by that one
+    let mut resolved_names = std::collections::HashMap::new();
+    let mut resolutions = Vec::new();
+    let log_names = LogNames::new(label, parsed, mapper);
     
     println!("=== STATUS LOOKUP DEBUG ===");
     println!("Looking up status for {} test names", names.len());
@@ -1826,20 +3079,14 @@ fn status_lookup(names: &[String], parsed: &ParsedLog) -> std::collections::Hash
             "passed".to_string()
         } else if parsed.ignored.contains(name) {
             "ignored".to_string()
-        } else {
-            // Debug: Check for partial matches to understand the mismatch
-            let partial_matches: Vec<&String> = parsed.passed.iter()
-                .chain(parsed.failed.iter())
-                .chain(parsed.ignored.iter())
-                .filter(|test| test.contains(name) || name.contains(*test))
-                .collect();
-            
-            if !partial_matches.is_empty() {
-                println!("MISMATCH: '{}' not found exactly, but found partial matches: {:?}", name, partial_matches);
-            } else {
-                println!("MISSING: '{}' not found at all", name);
+        } else if let Some(resolved) = log_names.resolve(name) {
+            resolutions.push(resolved.resolution);
+            if let Some(log_name) = resolved.log_name {
+                resolved_names.insert(name.clone(), log_name);
             }
-            
+            resolved.status.unwrap_or_else(|| "missing".to_string())
+        } else {
+            println!("MISSING: '{}' not found at all", name);
             "missing".to_string()
         };
         
@@ -1847,6 +3094,109 @@ fn status_lookup(names: &[String], parsed: &ParsedLog) -> std::collections::Hash
     }
     
     println!("=== END STATUS LOOKUP ===");
+    (out, resolved_names, resolutions)
+}
+
+fn longer_run(before: Option<f64>, after: Option<f64>) -> Option<f64> {
+    match (before, after) {
+        (Some(before), Some(after)) => Some(before.max(after)),
+        (before, after) => before.or(after),
+    }
+}
+
+// The slowest timed F2P/P2P tests by their longer run, plus every one over the threshold
+fn slow_tests(
+    fail_to_pass: &[String],
+    pass_to_pass: &[String],
+    before: &ParsedLog,
+    after: &ParsedLog,
+    log_names: &std::collections::HashMap<&'static str, std::collections::HashMap<String, String>>,
+    threshold: f64,
+) -> Vec<SlowTest> {
+    let tests = fail_to_pass.iter().map(|t| (t, "f2p")).chain(pass_to_pass.iter().map(|t| (t, "p2p")));
+    let mut timed: Vec<SlowTest> = tests
+        .map(|(test, kind)| {
+            let before = before.durations.get(log_name_of(log_names, "before", test)).copied();
+            let after = after.durations.get(log_name_of(log_names, "after", test)).copied();
+            SlowTest {
+                test: test.clone(),
+                kind: kind.to_string(),
+                before,
+                after,
+                over_threshold: longer_run(before, after).is_some_and(|seconds| seconds > threshold),
+            }
+        })
+        .filter(|slow| slow.before.is_some() || slow.after.is_some())
+        .collect();
+    let longest = |slow: &SlowTest| longer_run(slow.before, slow.after).unwrap_or_default();
+    timed.sort_by(|a, b| longest(b).total_cmp(&longest(a)).then_with(|| a.test.cmp(&b.test)));
+    let mut listed = 0;
+    timed.retain(|slow| {
+        listed += 1;
+        listed <= SLOW_TEST_LIMIT || slow.over_threshold
+    });
+    timed
+}
+
+/// F2P/P2P names that more than one test binary reported in the same log. Their status is the
+/// merged one (a failure anywhere wins), so the reviewer has to check which binary was meant.
+fn binary_collisions(names: &[String], logs: &[(&str, &ParsedLog)]) -> Vec<BinaryCollision> {
+    let mut out = Vec::new();
+    for name in names {
+        for (label, parsed) in logs {
+            if let Some(per_binary) = parsed.binaries.get(name).filter(|per_binary| per_binary.len() > 1) {
+                out.push(BinaryCollision { test: name.clone(), log: label.to_string(), binaries: per_binary.clone() });
+            }
+        }
+    }
+    out
+}
+
+// Explains, per test and log, which parse events led to the status `status_lookup` reports
+fn build_status_trace(names: &[String], logs: &[(&str, &ParsedLog)], mapper: &NameMapper) -> BTreeMap<String, Vec<StatusTrace>> {
+    let mut out = BTreeMap::new();
+    let log_names: Vec<_> = logs.iter().map(|(label, parsed)| LogNames::new(label, parsed, mapper)).collect();
+    for name in names {
+        let traces = logs.iter().zip(&log_names).map(|((label, parsed), log_names)| {
+            let recorded: Vec<&str> = [("failed", &parsed.failed), ("passed", &parsed.passed), ("ignored", &parsed.ignored)]
+                .iter()
+                .filter(|(_, set)| set.contains(name))
+                .map(|(status, _)| *status)
+                .collect();
+            // Same precedence as status_lookup: failed, then passed, then ignored
+            let qualified = if recorded.is_empty() { log_names.resolve(name) } else { None };
+            let (status, mut decision) = match (recorded.as_slice(), qualified.as_ref().map(|q| (&q.status, &q.resolution))) {
+                ([], Some((Some(status), resolution))) => (status.as_str(), format!(
+                    "no exact match; resolved to {} by {} (confidence {:.2})",
+                    resolution.matched.as_deref().unwrap_or_default(), resolution.strategy, resolution.confidence,
+                )),
+                ([], Some((None, resolution))) if resolution.strategy == "partial" => ("missing", format!(
+                    "no exact match; partial name matches: {:?}", resolution.candidates,
+                )),
+                ([], Some((None, resolution))) => ("missing", format!(
+                    "no exact match; ambiguous after {} normalization, candidates: {:?}", resolution.strategy, resolution.candidates,
+                )),
+                ([], None) => ("missing", "no status line matched this test name".to_string()),
+                ([only], _) => (*only, format!("recorded only as {}", only)),
+                ([first, rest @ ..], _) => (*first, format!("{} and {} both recorded; {} takes precedence", first, rest.join(" and "), first)),
+            };
+            if let Some(per_binary) = parsed.binaries.get(name).filter(|per_binary| per_binary.len() > 1) {
+                let reported: Vec<String> = per_binary.iter().map(|(binary, status)| format!("{} {}", binary, status)).collect();
+                decision.push_str(&format!("; reported by several test binaries ({})", reported.join(", ")));
+            }
+            StatusTrace {
+                log: label.to_string(),
+                status: status.to_string(),
+                parser: parsed.parser.clone(),
+                decision,
+                events: parsed.trace.iter()
+                    .filter(|event| &event.test == name || (qualified.is_some() && name.ends_with(event.test.as_str())))
+                    .cloned()
+                    .collect(),
+            }
+        }).collect();
+        out.insert(name.clone(), traces);
+    }
     out
 }
 
@@ -1961,14 +3311,26 @@ fn generate_analysis_result(
     agent_path: Option<&String>,
     report_data: Option<&serde_json::Value>,
     file_paths: &[String],
-) -> serde_json::Value {
+    rule_configs: &BTreeMap<String, RuleConfig>,
+    mapper: &NameMapper,
+    slow_test_threshold: f64,
+) -> AnalysisReport {
     let universe: Vec<String> = pass_to_pass.iter().chain(fail_to_pass.iter()).cloned().collect();
     
-    let base_s = status_lookup(&universe, base_parsed);
-    let before_s = status_lookup(&universe, before_parsed);
-    let after_s = status_lookup(&universe, after_parsed);
+    let mut log_names = std::collections::HashMap::new();
+    let (base_s, base_names, mut name_resolutions) = status_lookup(&universe, "base", base_parsed, mapper);
+    let (before_s, before_names, before_resolutions) = status_lookup(&universe, "before", before_parsed, mapper);
+    let (after_s, after_names, after_resolutions) = status_lookup(&universe, "after", after_parsed, mapper);
+    log_names.insert("base", base_names);
+    log_names.insert("before", before_names);
+    log_names.insert("after", after_names);
+    name_resolutions.extend(before_resolutions);
+    name_resolutions.extend(after_resolutions);
     let agent_s = if let Some(agent_parsed) = agent_parsed {
-        status_lookup(&universe, agent_parsed)
+        let (agent_s, agent_names, agent_resolutions) = status_lookup(&universe, "agent", agent_parsed, mapper);
+        log_names.insert("agent", agent_names);
+        name_resolutions.extend(agent_resolutions);
+        agent_s
     } else {
         std::collections::HashMap::new()
     };
@@ -1980,296 +3342,42 @@ fn generate_analysis_result(
         std::collections::HashMap::new()
     };
     
-    // ---------------- Rule checks parity ----------------
-    let c1_hits: Vec<String> = pass_to_pass.iter()
-        .filter(|t| base_s.get(*t) == Some(&"failed".to_string()))
-        .cloned()
-        .collect();
-    let c1 = !c1_hits.is_empty();
-    
-    // C2: failed in after (not: "not passed")
-    let c2_hits: Vec<String> = universe.iter()
-        .filter(|t| after_s.get(*t) == Some(&"failed".to_string()))
-        .cloned()
-        .collect();
-    let c2 = !c2_hits.is_empty();
-    
-    let c3_hits: Vec<String> = fail_to_pass.iter()
-        .filter(|t| before_s.get(*t) == Some(&"passed".to_string()))
-        .cloned()
-        .collect();
-    let c3 = !c3_hits.is_empty();
-    
-    // C4: P2P tests that are missing in base and not passing in before
-    // Logic:
-    // - If P2P passed in base → Skip (don't check)
-    // - If P2P is missing in base → Check before:
-    //   - If passing in before → No violation
-    //   - If missing or failed in before → Violation
-    let mut c4_hits: Vec<String> = vec![];
-    for t in pass_to_pass {
-        let b = base_s.get(t).map(String::as_str).unwrap_or("missing");
-        let be = before_s.get(t).map(String::as_str).unwrap_or("missing");
-        
-        // If P2P passed in base, skip this test (no need to check before)
-        if b == "passed" {
-            continue;
-        }
-        
-        // If P2P is missing in base, check it in before
-        if b == "missing" {
-            // If P2P is NOT passing in before (missing or failed), it's a violation
-            if be != "passed" {
-                c4_hits.push(format!("{t} (missing in base, {be} in before)"));
-            }
-        }
-    }
-    let c4 = !c4_hits.is_empty();
-    
-    // C5: true duplicates per log using enhanced detection
-    let mut dup_map = serde_json::Map::new();
-    let base_txt = fs::read_to_string(base_path).unwrap_or_default();
-    let before_txt = fs::read_to_string(before_path).unwrap_or_default();
-    let after_txt = fs::read_to_string(after_path).unwrap_or_default();
-    let base_dups = detect_same_file_duplicates(&base_txt);
-    let before_dups = detect_same_file_duplicates(&before_txt);
-    let after_dups = detect_same_file_duplicates(&after_txt);
-    if !base_dups.is_empty() {
-        dup_map.insert("base".to_string(), serde_json::Value::Array(base_dups.into_iter().take(50).map(serde_json::Value::String).collect()));
-    }
-    if !before_dups.is_empty() {
-        dup_map.insert("before".to_string(), serde_json::Value::Array(before_dups.into_iter().take(50).map(serde_json::Value::String).collect()));
-    }
-    if !after_dups.is_empty() {
-        dup_map.insert("after".to_string(), serde_json::Value::Array(after_dups.into_iter().take(50).map(serde_json::Value::String).collect()));
-    }
-    let c5 = !dup_map.is_empty();
-    
-    // C6: Test marked as failing in report.json but passing in post_agent_log
-    // This checks for inconsistencies between report.json and agent log results
-    let mut c6_hits: Vec<String> = vec![];
-    // C7: F2P tests found in golden source diff files but not in test diff files
-    let mut c7_hits: Vec<String> = vec![];
-    let c7 = {
-        println!("Performing C7 check: looking for F2P tests in golden source diff files (but not in test diffs)");
-        
-        // Find diff/patch files from patches folder
-        let diff_files: Vec<&String> = file_paths.iter()
-            .filter(|path| {
-                let path_lower = path.to_lowercase();
-                path_lower.contains("patches/") && (path_lower.ends_with(".diff") || path_lower.ends_with(".patch"))
-            })
-            .collect();
-        
-        println!("Found {} diff/patch files", diff_files.len());
-        
-        if !diff_files.is_empty() {
-            // Separate golden source diffs from test diffs
-            let (golden_source_diffs, test_diffs): (Vec<&String>, Vec<&String>) = diff_files.iter()
-                .partition(|path| {
-                    let filename = path.split('/').last().unwrap_or("").to_lowercase();
-                    // Golden source diffs typically contain "gold", "golden", "src", "source"
-                    // Test diffs typically contain "test"
-                    (filename.contains("gold") || filename.contains("src") || filename.contains("source")) &&
-                    !filename.contains("test")
-                });
-            
-            println!("Found {} golden source diff files and {} test diff files", 
-                     golden_source_diffs.len(), test_diffs.len());
-            
-            // Read all test diff contents to check if tests appear there
-            let mut test_diff_contents = String::new();
-            for test_diff in &test_diffs {
-                if let Ok(content) = fs::read_to_string(test_diff) {
-                    test_diff_contents.push_str(&content);
-                    test_diff_contents.push('\n');
-                    println!("Read test diff file: {}", test_diff);
-                }
-            }
-            
-            // Check golden source diffs for F2P tests
-            for golden_diff in &golden_source_diffs {
-                println!("Checking golden source diff file: {}", golden_diff);
-                
-                if let Ok(diff_content) = fs::read_to_string(golden_diff) {
-                    println!("Read golden source diff successfully, {} bytes", diff_content.len());
-                    
-                    // Check if any F2P test names appear in this golden source diff
-                    for f2p_test in fail_to_pass {
-                        // Extract the actual test name from module path (e.g., "tests::test_example" -> "test_example")
-                        let test_name_to_search = if f2p_test.contains("::") {
-                            f2p_test.split("::").last().unwrap_or(f2p_test)
-                        } else {
-                            f2p_test
-                        };
-                        
-                        if diff_content.contains(test_name_to_search) {
-                            // Check if this test also appears in test diffs as an actual test function
-                            let found_exact_test_in_test_diffs = if !test_diff_contents.is_empty() {
-                                // Normalize line endings to handle CRLF, LF, etc.
-                                let normalized_test_diff = test_diff_contents.replace("\r\n", "\n").replace("\r", "\n");
-                                
-                                // Look for exact test function patterns in test diffs
-                                // Use regex-like matching to handle whitespace and line endings flexibly
-                                let found_direct_fn = normalized_test_diff.contains(&format!("fn {}(", test_name_to_search)) ||
-                                                     normalized_test_diff.contains(&format!("fn {} (", test_name_to_search));
-                                
-                                // Look for #[test] attribute followed by the function (with flexible whitespace/newlines)
-                                let found_test_attribute = {
-                                    let lines: Vec<&str> = normalized_test_diff.lines().collect();
-                                    let mut found = false;
-                                    for i in 0..lines.len().saturating_sub(1) {
-                                        if lines[i].trim() == "#[test]" {
-                                            // Check next few lines for the function
-                                            for j in (i + 1)..std::cmp::min(i + 4, lines.len()) {
-                                                let line = lines[j].trim();
-                                                if line.starts_with(&format!("fn {}(", test_name_to_search)) ||
-                                                   line.starts_with(&format!("fn {} (", test_name_to_search)) {
-                                                    found = true;
-                                                    break;
-                                                }
-                                            }
-                                            if found { break; }
-                                        }
-                                    }
-                                    found
-                                };
-                                
-                                found_direct_fn || found_test_attribute
-                            } else {
-                                false
-                            };
-                            
-                            if found_exact_test_in_test_diffs {
-                                println!("F2P test '{}' found in both golden source and test diffs as actual test function - not a violation", f2p_test);
-                            } else {
-                                let violation = format!("{} (found as '{}' in {} but not as actual test function in test diffs)", 
-                                                      f2p_test, test_name_to_search, 
-                                                      golden_diff.split('/').last().unwrap_or(golden_diff));
-                                c7_hits.push(violation);
-                                println!("C7 violation: F2P test '{}' found as '{}' in golden source diff '{}' but not as actual test function in test diffs", 
-                                         f2p_test, test_name_to_search, golden_diff);
-                            }
-                        }
-                    }
-                } else {
-                    println!("Failed to read golden source diff file: {}", golden_diff);
-                }
-            }
-        } else {
-            println!("No diff/patch files found in patches folder");
-        }
-        
-        let has_violations = !c7_hits.is_empty();
-        println!("C7 check completed: {} violations found", c7_hits.len());
-        has_violations
-    };
-
-    let c6 = if let (Some(_agent_parsed), Some(report_data)) = (agent_parsed, report_data) {
-        println!("Performing C6 check: comparing report.json with agent log results");
-        
-        // Parse report.json to extract test results
-        // Common formats: results array, test_results array, direct test mapping, or SWE-bench format
-        let mut report_failed_tests = std::collections::HashSet::new();
-        
-        // Try different possible structures for report.json
-        if let Some(results_array) = report_data.get("results").and_then(|r| r.as_array()) {
-            for result in results_array {
-                if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
-                    if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                        report_failed_tests.insert(test_name.to_string());
-                    }
-                }
-            }
-        } else if let Some(test_results) = report_data.get("test_results").and_then(|r| r.as_array()) {
-            for result in test_results {
-                if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
-                    if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                        report_failed_tests.insert(test_name.to_string());
-                    }
-                }
-            }
-        } else if let Some(tests_obj) = report_data.get("tests").and_then(|t| t.as_object()) {
-            // Format: {"tests": {"test_name": {"status": "failed"}}}
-            for (test_name, test_data) in tests_obj {
-                if let Some(status) = test_data.get("status").and_then(|s| s.as_str()) {
-                    if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                        report_failed_tests.insert(test_name.clone());
-                    }
-                }
-            }
-        } else if let Some(obj) = report_data.as_object() {
-            // Check for SWE-bench format first
-            let mut found_swe_format = false;
-            for (key, value) in obj {
-                if let Some(tests_status) = value.get("tests_status").and_then(|t| t.as_object()) {
-                    println!("Found SWE-bench format report.json for key: {}", key);
-                    found_swe_format = true;
-                    
-                    // Parse all test categories that indicate failure
-                    for (category, category_data) in tests_status {
-                        if let Some(category_obj) = category_data.as_object() {
-                            // Extract failed tests from "failure" arrays in all categories
-                            if let Some(failure_array) = category_obj.get("failure").and_then(|f| f.as_array()) {
-                                for test_item in failure_array {
-                                    if let Some(test_name) = test_item.as_str() {
-                                        report_failed_tests.insert(test_name.to_string());
-                                        println!("Found failed test in category {}: {}", category, test_name);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    break; // Found SWE-bench format, no need to check other keys
-                }
-            }
-            
-            // If not SWE-bench format, try direct mapping format: {"test_name": "status"}
-            if !found_swe_format {
-                for (test_name, status_val) in obj {
-                    if let Some(status) = status_val.as_str() {
-                        if status.to_lowercase() == "failed" || status.to_lowercase() == "fail" {
-                            report_failed_tests.insert(test_name.clone());
-                        }
-                    }
-                }
-            }
-        }
-        
-        println!("Found {} failed tests in report.json", report_failed_tests.len());
-        
-        // Check F2P and P2P tests for inconsistencies in both directions
-        for test_name in &universe {
-            let report_status = if report_failed_tests.contains(test_name) {
-                "failed"
-            } else if report_s.get(test_name) == Some(&"passed".to_string()) {
-                "passed"
-            } else {
-                "missing" // Skip tests that are missing in report.json
-            };
-            
-            let agent_status = agent_s.get(test_name).map(String::as_str).unwrap_or("missing");
-            
-            // Check for status mismatches (excluding missing cases)
-            if report_status != "missing" && agent_status != "missing" && report_status != agent_status {
-                match (report_status, agent_status) {
-                    ("failed", "passed") => {
-                        c6_hits.push(format!("{} (marked as failed in report.json but passing in agent log)", test_name));
-                    },
-                    ("passed", "failed") => {
-                        c6_hits.push(format!("{} (marked as passed in report.json but failing in agent log)", test_name));
-                    },
-                    _ => {} // Other combinations like "passed" vs "ignored" could be added if needed
-                }
-            }
-        }
-        
-        println!("C6 check found {} inconsistencies", c6_hits.len());
-        !c6_hits.is_empty()
-    } else {
-        println!("C6 check skipped: missing agent log or report.json");
-        false
+    // ---------------- Rule checks ----------------
+    let ctx = AnalysisContext {
+        fail_to_pass,
+        pass_to_pass,
+        universe,
+        base_parsed,
+        before_parsed,
+        after_parsed,
+        agent_parsed,
+        base_status: base_s,
+        before_status: before_s,
+        after_status: after_s,
+        agent_status: agent_s,
+        report_status: report_s,
+        log_names,
+        base_path,
+        before_path,
+        after_path,
+        agent_path: agent_path.map(|p| p.as_str()),
+        report_data,
+        file_paths,
+        slow_test_threshold,
     };
+    let registry = RuleRegistry::with_default_rules();
+    let rule_checks = registry.evaluate(&ctx, rule_configs);
+    let verdict = compute_verdict(&registry, &rule_checks);
+    let AnalysisContext {
+        base_status: base_s,
+        before_status: before_s,
+        after_status: after_s,
+        agent_status: agent_s,
+        report_status: report_s,
+        log_names,
+        universe,
+        ..
+    } = ctx;
     
     // P2P rejection logic
     let p2p_ignored: Vec<String> = pass_to_pass.iter()
@@ -2314,206 +3422,135 @@ fn generate_analysis_result(
     
     let rejection_satisfied = !p2p_rejected.is_empty();
     
-    // Generate p2p_analysis
-    let mut p2p_analysis = serde_json::Map::new();
-    for test_name in pass_to_pass {
-        let mut test_data = serde_json::Map::new();
-        test_data.insert("base".to_string(), serde_json::Value::String(base_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("before".to_string(), serde_json::Value::String(before_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("after".to_string(), serde_json::Value::String(after_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("agent".to_string(), serde_json::Value::String(agent_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("report".to_string(), serde_json::Value::String(report_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        p2p_analysis.insert(test_name.clone(), serde_json::Value::Object(test_data));
-    }
-    
-    // Generate f2p_analysis
-    let mut f2p_analysis = serde_json::Map::new();
-    for test_name in fail_to_pass {
-        let mut test_data = serde_json::Map::new();
-        test_data.insert("base".to_string(), serde_json::Value::String(base_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("before".to_string(), serde_json::Value::String(before_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("after".to_string(), serde_json::Value::String(after_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("agent".to_string(), serde_json::Value::String(agent_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        test_data.insert("report".to_string(), serde_json::Value::String(report_s.get(test_name).unwrap_or(&"missing".to_string()).clone()));
-        f2p_analysis.insert(test_name.clone(), serde_json::Value::Object(test_data));
-    }
+    // Generate p2p_analysis and f2p_analysis
+    let mut logs = vec![("base", base_parsed), ("before", before_parsed), ("after", after_parsed)];
+    if let Some(agent_parsed) = agent_parsed {
+        logs.push(("agent", agent_parsed));
+    }
+    let status_row = |test_name: &String| TestStatusRow {
+        base: base_s.get(test_name).cloned().unwrap_or_else(|| "missing".to_string()),
+        before: before_s.get(test_name).cloned().unwrap_or_else(|| "missing".to_string()),
+        after: after_s.get(test_name).cloned().unwrap_or_else(|| "missing".to_string()),
+        agent: agent_s.get(test_name).cloned().unwrap_or_else(|| "missing".to_string()),
+        report: report_s.get(test_name).cloned().unwrap_or_else(|| "missing".to_string()),
+        failures: logs.iter()
+            .filter_map(|(label, parsed)| {
+                let detail = parsed.failures.get(log_name_of(&log_names, label, test_name))?;
+                Some((label.to_string(), detail.clone()))
+            })
+            .collect(),
+        doctest: parse_doctest_name(test_name).is_some(),
+    };
+    let p2p_analysis: BTreeMap<String, TestStatusRow> = pass_to_pass.iter()
+        .map(|test_name| (test_name.clone(), status_row(test_name)))
+        .collect();
+    let f2p_analysis: BTreeMap<String, TestStatusRow> = fail_to_pass.iter()
+        .map(|test_name| (test_name.clone(), status_row(test_name)))
+        .collect();
     
     // Generate debug_log_counts
+    let log_counts = |label: &str, parsed: &ParsedLog| LogCounts {
+        label: label.to_string(),
+        passed: parsed.passed.len(),
+        failed: parsed.failed.len(),
+        ignored: parsed.ignored.len(),
+        all: parsed.all.len(),
+        parser: parsed.parser.clone(),
+        confidence: parsed.confidence,
+        detection_scores: parsed.detection_scores.clone(),
+        overridden: parsed.overridden,
+        outcome: parsed.outcome,
+        outcome_reason: parsed.outcome_reason.clone(),
+        summary: summary_totals(parsed),
+    };
     let mut debug_log_counts = vec![
-        serde_json::json!({
-            "label": "base",
-            "passed": base_parsed.passed.len(),
-            "failed": base_parsed.failed.len(),
-            "ignored": base_parsed.ignored.len(),
-            "all": base_parsed.all.len(),
-        }),
-        serde_json::json!({
-            "label": "before",
-            "passed": before_parsed.passed.len(),
-            "failed": before_parsed.failed.len(),
-            "ignored": before_parsed.ignored.len(),
-            "all": before_parsed.all.len(),
-        }),
-        serde_json::json!({
-            "label": "after",
-            "passed": after_parsed.passed.len(),
-            "failed": after_parsed.failed.len(),
-            "ignored": after_parsed.ignored.len(),
-            "all": after_parsed.all.len(),
-        }),
+        log_counts("base", base_parsed),
+        log_counts("before", before_parsed),
+        log_counts("after", after_parsed),
     ];
     if let Some(agent_parsed) = agent_parsed {
-        debug_log_counts.push(serde_json::json!({
-            "label": "agent",
-            "passed": agent_parsed.passed.len(),
-            "failed": agent_parsed.failed.len(),
-            "ignored": agent_parsed.ignored.len(),
-            "all": agent_parsed.all.len(),
-        }));
-    }
-    
-    serde_json::json!({
-        "inputs": {
-            "base_log": base_path,
-            "before_log": before_path,
-            "after_log": after_path,
-            "agent_log": agent_path.map(|p| p.as_str()).unwrap_or(""),
+        debug_log_counts.push(log_counts("agent", agent_parsed));
+    }
+    let parser_health: Vec<String> = logs.iter()
+        .filter_map(|(label, parsed)| parser_health_warning(label, parsed))
+        .collect();
+    for warning in &parser_health {
+        println!("Parser health warning: {}", warning);
+    }
+    let slow_tests = slow_tests(fail_to_pass, pass_to_pass, before_parsed, after_parsed, &log_names, slow_test_threshold);
+    let binary_collisions = binary_collisions(&universe, &logs);
+    for collision in &binary_collisions {
+        println!("Test name collision in {}.log: '{}' reported by {:?}", collision.log, collision.test, collision.binaries);
+    }
+    
+    AnalysisReport {
+        version: ANALYSIS_REPORT_VERSION,
+        inputs: ReportInputs {
+            base_log: base_path.to_string(),
+            before_log: before_path.to_string(),
+            after_log: after_path.to_string(),
+            agent_log: agent_path.cloned().unwrap_or_default(),
         },
-        "counts": {
-            "P2P": pass_to_pass.len(),
-            "F2P": fail_to_pass.len()
+        counts: TestCounts {
+            p2p: pass_to_pass.len(),
+            f2p: fail_to_pass.len(),
         },
-        "rule_checks": {
-            "c1_failed_in_base_present_in_P2P": {
-                "has_problem": c1,
-                "examples": c1_hits
-            },
-            "c2_failed_in_after_present_in_F2P_or_P2P": {
-                "has_problem": c2,
-                "examples": c2_hits
-            },
-            "c3_F2P_success_in_before": {
-                "has_problem": c3,
-                "examples": c3_hits
-            },
-            "c4_P2P_missing_in_base_and_not_passing_in_before": {
-                "has_problem": c4,
-                "examples": c4_hits
-            },
-            "c5_duplicates_in_same_log_for_F2P_or_P2P": {
-                "has_problem": c5,
-                "duplicate_examples_per_log": serde_json::Value::Object(dup_map)
-            },
-            "c6_test_marked_failed_in_report_but_passing_in_agent": {
-                "has_problem": c6,
-                "examples": c6_hits
-            },
-            "c7_f2p_tests_in_golden_source_diff": {
-                "has_problem": c7,
-                "examples": c7_hits
-            },
-        },
-        "rejection_reason": {
-            "satisfied": rejection_satisfied,
-            "p2p_ignored_because_passed_in_base_and_after": p2p_ignored,
-            "p2p_considered": p2p_considered,
-            "p2p_rejected": p2p_rejected,
-            "p2p_considered_but_ok": p2p_ok,
-            "f2p_ignored_because_passed_in_after": f2p_ignored,
-            "f2p_considered": f2p_considered,
-            "f2p_rejected": f2p_rejected,
-            "f2p_considered_but_ok": f2p_ok,
+        rule_checks,
+        rejection_reason: RejectionReason {
+            satisfied: rejection_satisfied,
+            p2p_ignored_because_passed_in_base_and_after: p2p_ignored,
+            p2p_considered,
+            p2p_rejected,
+            p2p_considered_but_ok: p2p_ok,
+            f2p_ignored_because_passed_in_after: f2p_ignored,
+            f2p_considered,
+            f2p_rejected,
+            f2p_considered_but_ok: f2p_ok,
         },
-        "p2p_analysis": p2p_analysis,
-        "f2p_analysis": f2p_analysis,
-        "debug_log_counts": serde_json::Value::Array(debug_log_counts)
-    })
-}
-
-// Function to extract clean test name from nextest line
-// This tries to intelligently parse different nextest formats without hardcoding specific crates
-fn extract_test_name_from_nextest_line(full_line: &str) -> String {
-    let trimmed = full_line.trim();
-    
-    println!("EXTRACT DEBUG: input='{}'", trimmed);
-    
-    // Simple approach: Just return the full test name as captured by regex
-    // The nextest format is: "PASS [time] full_test_name"
-    // We should preserve the full test name exactly as it appears
-    
-    // Special handling for known patterns in main.json:
-    // 1. "miden-testing kernel_tests::..." -> keep as is
-    // 2. "miden-testing::miden-integration-tests ..." -> keep as is  
-    // 3. "miden-lib ..." -> keep as is
-    // 4. "miden-objects ..." -> keep as is
-    // 5. "miden-tx ..." -> keep as is (NEW - this was missing!)
-    
-    // For miden crates, the format in main.json matches exactly what's in the log
-    if trimmed.starts_with("miden-") {
-        let result = trimmed.to_string();
-        println!("EXTRACT DEBUG: Miden crate, keeping as-is='{}'", result);
-        return result;
-    }
-    
-    // Check for double crate format: "miden-testing::miden-integration-tests scripts::faucet::test"
-    if trimmed.contains("::miden-integration-tests ") {
-        let result = trimmed.to_string();
-        println!("EXTRACT DEBUG: Double crate format, keeping as-is='{}'", result);
-        return result;
+        p2p_analysis,
+        f2p_analysis,
+        debug_log_counts,
+        verdict,
+        trace: None,
+        parser_health,
+        binary_collisions,
+        name_resolutions,
+        slow_tests,
     }
-    
-    // Check for crate::lib format: "grillon::lib assert::json_path..." -> just the test part
-    if trimmed.contains("::lib ") {
-        if let Some(lib_pos) = trimmed.find("::lib ") {
-            let result = trimmed[lib_pos + 6..].trim().to_string(); // 6 = len("::lib ")
-            println!("EXTRACT DEBUG: crate::lib format, extracting test part='{}'", result);
-            return result;
-        }
-    }
-    
-    // For other formats, check if there's a space and we should remove the crate prefix
-    if let Some(space_pos) = trimmed.find(' ') {
-        let crate_part = &trimmed[..space_pos];
-        let test_part = &trimmed[space_pos + 1..];
-        
-        // If the crate part doesn't contain "::" and the test part does, remove the crate prefix
-        if !crate_part.contains("::") && test_part.contains("::") {
-            let result = test_part.trim().to_string();
-            println!("EXTRACT DEBUG: Generic crate format, removing prefix='{}'", result);
-            return result;
-        }
-    }
-    
-    // If no patterns match, return the original
-    let result = trimmed.to_string();
-    println!("EXTRACT DEBUG: no pattern matched, keeping original='{}'", result);
-    result
 }
 
+// Time nextest reports for a result, "[   2.877s]"
+fn nextest_seconds(line: &str) -> Option<f64> {
+    NEXTEST_TIME_RE.captures(line)?[1].parse().ok()
+}
 
+/// Splits a nextest result id into its binary id and test name: `my-crate::integration tests::a`
+/// becomes `(Some("my-crate::integration"), "tests::a")`. Older nextest output has no binary id.
+fn split_nextest_id(full_match: &str) -> (Option<&str>, String) {
+    match full_match.trim().split_once(' ') {
+        Some((binary, test)) => (Some(binary), test.trim().to_string()),
+        None => (None, full_match.trim().to_string()),
+    }
+}
 
 // Test function to verify nextest parsing
 #[tauri::command]
 pub fn test_nextest_parsing() -> Result<String, String> {
-    let test_content = r#"PASS [   0.021s] grillon assertion::impls::json_path::tests::is_eq::impl_is_eq_object_with_array_and_object
-PASS [   0.155s] grillon::lib assert::json_path::json_path_does_not_match
-PASS [   0.028s] miden-lib account::interface::test::test_basic_wallet_default_notes
-PASS [   0.034s] miden-testing kernel_tests::tx::test_account_delta::storage_delta_for_map_slots
-PASS [   0.045s] miden-testing::miden-integration-tests scripts::faucet::faucet_contract_mint_fungible_asset_fails_exceeds_max_supply
-PASS [   2.877s] miden-tx auth::tx_authenticator::test::serialize_auth_key"#;
+    let test_content = r#"PASS [   0.021s] my-crate tests::is_eq::object_with_array
+PASS [   0.155s] my-crate::lib assert::json_path_does_not_match
+PASS [   0.034s] my-crate::integration scripts::faucet::mint_exceeds_max_supply
+PASS [   0.045s] my-crate::bin/cli args::parse_defaults
+PASS [   2.877s] other-crate auth::test::serialize_auth_key"#;
     
     println!("=== TESTING NEXTEST PARSING ===");
     
-    // Test the nextest format detection
-    let is_nextest = looks_nextest_format(test_content);
-    println!("Detected as nextest format: {}", is_nextest);
+    // Test the format detection
+    let registry = ParserRegistry::with_default_parsers();
+    println!("Detection scores: {:?}", registry.detect(test_content));
     
     // Parse the content
-    let parsed = if is_nextest {
-        parse_nextest_log(test_content)
-    } else {
-        parse_rust_log_single_line(test_content)
-    };
+    let parsed = registry.parse(test_content, false);
+    println!("Detected as {} format", parsed.parser);
     
     println!("Parsed results:");
     println!("  Passed: {} tests", parsed.passed.len());
@@ -2521,12 +3558,11 @@ PASS [   2.877s] miden-tx auth::tx_authenticator::test::serialize_auth_key"#;
         println!("    - {}", test);
     }
     
-    // Test specific extraction for the problematic case
-    println!("\n=== TESTING SPECIFIC EXTRACTION ===");
-    let test_line = "miden-tx auth::tx_authenticator::test::serialize_auth_key";
-    let extracted = extract_test_name_from_nextest_line(test_line);
-    println!("Input: '{}'", test_line);
-    println!("Extracted: '{}'", extracted);
+    // Binary id and test name of every result
+    println!("\n=== TESTING BINARY IDS ===");
+    for (test, per_binary) in &parsed.binaries {
+        println!("    {} -> {:?}", test, per_binary);
+    }
     
     Ok(format!("Parsed {} passed tests", parsed.passed.len()))
 }
@@ -0,0 +1,863 @@
+use crate::analysis::*;
+use crate::names::NameMapping;
+use crate::rules::{compute_score, RuleConfig, Severity};
+use std::fs;
+use tempfile::TempDir;
+
+// Writes a minimal deliverable (main.json + base/before/after logs) and returns its file paths
+fn write_deliverable(dir: &TempDir, main_json: &str, base: &str, before: &str, after: &str) -> Vec<String> {
+    let files = [
+        ("main/instance.json", main_json),
+        ("logs/instance_base.log", base),
+        ("logs/instance_before.log", before),
+        ("logs/instance_after.log", after),
+    ];
+    files.iter().map(|(name, content)| {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }).collect()
+}
+
+const MAIN_JSON: &str = r#"{"fail_to_pass": ["tests::new_feature"], "pass_to_pass": ["tests::existing"]}"#;
+
+#[tokio::test]
+async fn analyze_logs_returns_typed_report() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    assert_eq!(report.version, ANALYSIS_REPORT_VERSION);
+    assert_eq!(report.counts.f2p, 1);
+    assert_eq!(report.counts.p2p, 1);
+    assert!(report.violated_rules().is_empty(), "unexpected violations: {:?}", report.violated_rules());
+    assert_eq!(report.f2p_analysis["tests::new_feature"].before, "failed");
+    assert_eq!(report.f2p_analysis["tests::new_feature"].after, "passed");
+    assert_eq!(report.p2p_analysis["tests::existing"].agent, "missing");
+    assert_eq!(report.verdict.decision, Decision::Accept);
+    assert!(report.verdict.reasons.is_empty());
+}
+
+#[tokio::test]
+async fn analysis_report_keeps_legacy_json_keys() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... FAILED\n",
+        "test tests::new_feature ... ok\n",
+        "test tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+    assert!(report.has_problem(C1_FAILED_IN_BASE_PRESENT_IN_P2P));
+    assert!(report.has_problem(C3_F2P_SUCCESS_IN_BEFORE));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["counts"]["P2P"], 1);
+    assert_eq!(json["rule_checks"][C1_FAILED_IN_BASE_PRESENT_IN_P2P]["examples"][0], "tests::existing");
+    assert!(json["rule_checks"][C5_DUPLICATES_IN_SAME_LOG_FOR_F2P_OR_P2P]["duplicate_examples_per_log"].is_object());
+
+    let round_trip: AnalysisReport = serde_json::from_value(json).unwrap();
+    assert_eq!(round_trip.rule_checks.len(), report.rule_checks.len());
+}
+
+#[tokio::test]
+async fn rule_config_disables_rules_and_limits_examples() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["tests::a", "tests::b", "tests::c"], "pass_to_pass": ["tests::existing"]}"#;
+    let paths = write_deliverable(
+        &dir,
+        main_json,
+        "test tests::existing ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::a ... ok\ntest tests::b ... ok\ntest tests::c ... ok\n",
+        "test tests::existing ... ok\ntest tests::a ... ok\ntest tests::b ... ok\ntest tests::c ... ok\n",
+    );
+
+    let mut rule_configs = std::collections::BTreeMap::new();
+    rule_configs.insert(C1_FAILED_IN_BASE_PRESENT_IN_P2P.to_string(), RuleConfig { enabled: false, ..Default::default() });
+    rule_configs.insert(C3_F2P_SUCCESS_IN_BEFORE.to_string(), RuleConfig {
+        enabled: true,
+        severity: Some(Severity::Warning),
+        example_limit: Some(2),
+    });
+    let options = AnalysisOptions { rule_configs: Some(rule_configs), ..Default::default() };
+
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+
+    let c1 = report.rule(C1_FAILED_IN_BASE_PRESENT_IN_P2P).unwrap();
+    assert!(!c1.enabled);
+    assert!(!c1.has_problem);
+
+    let c3 = report.rule(C3_F2P_SUCCESS_IN_BEFORE).unwrap();
+    assert!(c3.has_problem);
+    assert_eq!(c3.severity, Severity::Warning);
+    assert_eq!(c3.example_limit, Some(2));
+    assert_eq!(c3.examples.len(), 2);
+    assert_eq!(c3.tests.len(), 3);
+
+    // C3 is the only failing rule and was downgraded to a warning
+    assert_eq!(report.verdict.decision, Decision::NeedsManualReview);
+    assert_eq!(report.verdict.reasons.len(), 1);
+    assert_eq!(report.verdict.reasons[0].rule_id, C3_F2P_SUCCESS_IN_BEFORE);
+}
+
+#[tokio::test]
+async fn verdict_orders_reasons_by_severity() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let mut rule_configs = std::collections::BTreeMap::new();
+    rule_configs.insert(C1_FAILED_IN_BASE_PRESENT_IN_P2P.to_string(), RuleConfig {
+        severity: Some(Severity::Warning),
+        ..Default::default()
+    });
+    let options = AnalysisOptions { rule_configs: Some(rule_configs), ..Default::default() };
+
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+
+    assert_eq!(report.verdict.decision, Decision::Reject);
+    let reasons: Vec<(&str, Severity)> = report.verdict.reasons.iter()
+        .map(|reason| (reason.rule_id.as_str(), reason.severity))
+        .collect();
+    assert_eq!(reasons, vec![
+        (C3_F2P_SUCCESS_IN_BEFORE, Severity::Error),
+        (C1_FAILED_IN_BASE_PRESENT_IN_P2P, Severity::Warning),
+    ]);
+    assert_eq!(report.verdict.reasons[0].tests, vec!["tests::new_feature"]);
+}
+
+#[test]
+fn detect_file_roles_assigns_each_log() {
+    let paths: Vec<String> = [
+        "/tmp/x/main/instance.json",
+        "/tmp/x/logs/instance_base.log",
+        "/tmp/x/logs/instance_before.log",
+        "/tmp/x/logs/instance_after.log",
+        "/tmp/x/logs/instance_post_agent_patch.log",
+        "/tmp/x/results/report.json",
+        "/tmp/x/patches/golden.diff",
+        "/tmp/x/fixtures/database.xml",
+    ].iter().map(|p| p.to_string()).collect();
+
+    let roles = detect_file_roles(&paths);
+
+    assert_eq!(roles.main_json.as_deref(), Some("/tmp/x/main/instance.json"));
+    assert_eq!(roles.after_log.as_deref(), Some("/tmp/x/logs/instance_after.log"));
+    assert_eq!(roles.agent_log.as_deref(), Some("/tmp/x/logs/instance_post_agent_patch.log"));
+    assert_eq!(roles.report_json.as_deref(), Some("/tmp/x/results/report.json"));
+    // "database" only ends in "base", it does not name the base role
+    assert!(roles.base_junit.is_none());
+    assert_eq!(roles.unrecognized, vec!["/tmp/x/patches/golden.diff", "/tmp/x/fixtures/database.xml"]);
+    assert!(roles.missing_required().is_empty());
+}
+
+#[tokio::test]
+async fn process_deliverable_scores_rule_outcomes() {
+    use crate::report_checker::{process_deliverable, FileInfo};
+
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+    let files: Vec<FileInfo> = paths.iter().enumerate()
+        .map(|(i, path)| FileInfo { id: i.to_string(), name: path.clone(), path: path.clone() })
+        .collect();
+
+    let result = process_deliverable(files).await.unwrap();
+
+    assert_eq!(result.status, "completed");
+    assert_eq!(result.files_processed, 4);
+    assert_eq!(result.issues_found, 1);
+    assert_eq!(result.decision, Some(Decision::Reject));
+    assert!(result.score < 100);
+    assert!(result.summary.contains(C1_FAILED_IN_BASE_PRESENT_IN_P2P));
+
+    let incomplete = process_deliverable(vec![FileInfo { id: "0".into(), name: "x".into(), path: paths[0].clone() }]).await.unwrap();
+    assert_eq!(incomplete.status, "incomplete");
+    assert!(incomplete.report.is_none());
+}
+
+#[tokio::test]
+async fn violations_carry_log_evidence() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "running 2 tests\ntest tests::existing ... FAILED\ntest tests::existing_edge_case ... FAILED\n\nfailures:\n    tests::existing\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let c1 = report.rule(C1_FAILED_IN_BASE_PRESENT_IN_P2P).unwrap();
+    // Only the status line of this exact test counts, not the "failures:" listing or a test
+    // whose name merely starts with it
+    assert_eq!(c1.evidence.len(), 1);
+    let evidence = &c1.evidence[0];
+    assert_eq!(evidence.test, "tests::existing");
+    assert_eq!(evidence.log, "base");
+    assert_eq!(evidence.line_number, 2);
+    assert_eq!(evidence.line_content, "test tests::existing ... FAILED");
+    assert_eq!(evidence.context_before, vec!["running 2 tests"]);
+
+    let c2 = report.rule(C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P).unwrap();
+    assert!(c2.evidence.is_empty());
+}
+
+#[tokio::test]
+async fn javascript_evidence_stays_within_its_describe_block() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["Header > renders title"], "pass_to_pass": ["Footer > renders header"]}"#;
+    let base = " FAIL  src/layout.test.js
+  Header
+    ✓ renders header (5 ms)
+  Footer
+    with links
+      ✓ renders header (1 ms)
+    ✕ renders header (3 ms)
+";
+    let after = " PASS  src/layout.test.js
+  Header
+    ✓ renders header (5 ms)
+    ✓ renders title (1 ms)
+  Footer
+    ✓ renders header (2 ms)
+";
+    let paths = write_deliverable(&dir, main_json, base, "", after);
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let c1 = report.rule(C1_FAILED_IN_BASE_PRESENT_IN_P2P).unwrap();
+    assert!(c1.has_problem);
+    let lines: Vec<usize> = c1.evidence.iter().map(|evidence| evidence.line_number).collect();
+    assert_eq!(lines, vec![7]);
+
+    let vitest = " ✓ src/math.test.ts (2 tests) 4ms
+   ✓ math (2)
+     ✓ adds 1ms
+   ✓ strings (1)
+     ✓ adds 1ms
+";
+    let hits: Vec<usize> = search_in_log_content(vitest, "strings > adds").iter().map(|hit| hit.line_number).collect();
+    assert_eq!(hits, vec![5]);
+}
+
+#[tokio::test]
+async fn trace_explains_status_precedence() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        "test tests::existing ... ok\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n\nfailures:\n    tests::new_feature\n",
+    );
+
+    let untraced = analyze_logs(paths.clone()).await.unwrap();
+    assert!(untraced.trace.is_none());
+
+    let options = AnalysisOptions { trace: true, ..Default::default() };
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+    let trace = report.trace.as_ref().unwrap();
+
+    let after = trace["tests::new_feature"].iter().find(|t| t.log == "after").unwrap();
+    assert_eq!(after.status, "failed");
+    assert_eq!(after.parser, "libtest");
+    assert_eq!(after.decision, "failed and passed both recorded; failed takes precedence");
+    let patterns: Vec<(&str, Option<usize>)> = after.events.iter()
+        .map(|event| (event.pattern.as_str(), event.line_number))
+        .collect();
+    assert_eq!(patterns, vec![("TEST_LINE_RE", Some(2)), ("FAILURES_BLOCK_RE", Some(5))]);
+
+    let before = trace["tests::new_feature"].iter().find(|t| t.log == "before").unwrap();
+    assert_eq!(before.status, "missing");
+    assert!(before.events.is_empty());
+}
+
+#[tokio::test]
+async fn parser_override_applies_to_one_log_only() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let mut parser_overrides = std::collections::BTreeMap::new();
+    parser_overrides.insert("after".to_string(), "nextest".to_string());
+    let options = AnalysisOptions { parser_overrides, ..Default::default() };
+    let report = analyze_logs_with_options(paths.clone(), options).await.unwrap();
+
+    let after = report.debug_log_counts.iter().find(|counts| counts.label == "after").unwrap();
+    assert_eq!(after.parser, "nextest");
+    assert!(after.overridden);
+    assert_eq!(after.confidence, after.detection_scores["nextest"]);
+    let before = report.debug_log_counts.iter().find(|counts| counts.label == "before").unwrap();
+    assert_eq!(before.parser, "libtest");
+    assert!(!before.overridden);
+    // The nextest parser also reads plain libtest lines, so the outcome is unchanged
+    assert_eq!(report.f2p_analysis["tests::new_feature"].after, "passed");
+
+    let mut parser_overrides = std::collections::BTreeMap::new();
+    parser_overrides.insert("after".to_string(), "pytest-ish".to_string());
+    let options = AnalysisOptions { parser_overrides, ..Default::default() };
+    let err = analyze_logs_with_options(paths, options).await.unwrap_err();
+    assert!(err.contains("Unknown log parser 'pytest-ish'"), "{}", err);
+}
+
+#[tokio::test]
+async fn python_deliverable_runs_the_same_rules() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["tests/test_api.py::test_parse[a b]"], "pass_to_pass": ["tests/test_api.py::TestClient::test_get"]}"#;
+    let paths = write_deliverable(
+        &dir,
+        main_json,
+        "tests/test_api.py::TestClient::test_get FAILED [100%]\n",
+        "tests/test_api.py::TestClient::test_get PASSED [ 50%]\ntests/test_api.py::test_parse[a b] FAILED [100%]\n",
+        "PASSED tests/test_api.py::TestClient::test_get\nPASSED tests/test_api.py::test_parse[a b]\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    assert!(report.debug_log_counts.iter().all(|counts| counts.parser == "pytest"));
+    assert_eq!(report.f2p_analysis["tests/test_api.py::test_parse[a b]"].after, "passed");
+    assert_eq!(report.violated_rules(), vec![C1_FAILED_IN_BASE_PRESENT_IN_P2P]);
+    let c1 = report.rule(C1_FAILED_IN_BASE_PRESENT_IN_P2P).unwrap();
+    assert_eq!(c1.evidence[0].line_number, 1);
+}
+
+#[tokio::test]
+async fn junit_report_takes_precedence_over_the_text_log() {
+    let dir = TempDir::new().unwrap();
+    let mut paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... FAILED\n",
+        // A truncated text log: the fixed test never reported
+        "test tests::existing ... ok\n",
+    );
+    let junit = dir.path().join("logs/instance_after_junit.xml");
+    fs::write(&junit, r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="crate" tests="3" failures="0">
+    <testcase name="tests::existing" classname="crate" time="0.001"/>
+    <testcase name="tests::new_feature" classname="crate" time="0.002">
+      <system-out>ok</system-out>
+    </testcase>
+    <testcase name="tests::slow" classname="crate"><skipped/></testcase>
+  </testsuite>
+</testsuites>
+"#).unwrap();
+    paths.push(junit.to_string_lossy().to_string());
+
+    let roles = detect_file_roles(&paths);
+    assert_eq!(roles.source("after"), roles.after_junit.as_ref());
+    assert!(roles.after_log.is_some());
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let after = report.debug_log_counts.iter().find(|counts| counts.label == "after").unwrap();
+    assert_eq!(after.parser, "junit");
+    assert_eq!(report.f2p_analysis["tests::new_feature"].after, "passed");
+    assert_eq!(report.p2p_analysis["tests::existing"].after, "passed");
+}
+
+#[tokio::test]
+async fn failed_tests_carry_panic_details() {
+    let dir = TempDir::new().unwrap();
+    let before = "\
+running 2 tests
+test tests::existing ... ok
+test tests::new_feature ... FAILED
+
+failures:
+
+---- tests::new_feature stdout ----
+parsing input
+thread 'tests::new_feature' panicked at src/lib.rs:42:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::new_feature
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        before,
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let row = &report.f2p_analysis["tests::new_feature"];
+    assert_eq!(row.before, "failed");
+    let detail = &row.failures["before"];
+    assert_eq!(detail.kind, FailureKind::Assertion);
+    assert_eq!(detail.location.as_deref(), Some("src/lib.rs:42:9"));
+    assert_eq!(detail.message.as_deref(), Some("assertion `left == right` failed\n  left: 1\n right: 2"));
+    assert!(detail.stdout.starts_with("parsing input\n"));
+    assert!(!row.failures.contains_key("after"));
+    assert!(report.p2p_analysis["tests::existing"].failures.is_empty());
+}
+
+#[tokio::test]
+async fn f2p_failing_in_before_on_a_missing_fixture_is_flagged() {
+    let dir = TempDir::new().unwrap();
+    let before = "\
+test tests::existing ... ok
+test tests::new_feature ... FAILED
+
+failures:
+
+---- tests::new_feature stdout ----
+thread 'tests::new_feature' panicked at tests/common.rs:8:43:
+missing fixture tests/fixtures/new_feature.json, run `make fixtures` first
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        before,
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    assert_eq!(report.f2p_analysis["tests::new_feature"].failures["before"].kind, FailureKind::Infrastructure);
+    let check = &report.rule_checks[C8_F2P_FAILING_IN_BEFORE_FOR_WRONG_REASON];
+    assert!(check.has_problem);
+    assert_eq!(check.severity, Severity::Warning);
+    assert!(check.examples[0].starts_with("tests::new_feature (infrastructure in before: missing fixture"));
+    assert_eq!(report.verdict.decision, Decision::NeedsManualReview);
+}
+
+const BUILD_FAILURE_LOG: &str = "\
+   Compiling demo v0.1.0 (/repo)
+error[E0425]: cannot find value `parser` in this scope
+  --> src/lib.rs:12:5
+   |
+12 |     parser.run()
+   |     ^^^^^^ not found in this scope
+
+error: could not compile `demo` (lib test) due to 1 previous error
+";
+
+#[tokio::test]
+async fn build_failure_in_before_is_expected_when_only_f2p_depends_on_it() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        r#"{"fail_to_pass": ["tests::new_feature"], "pass_to_pass": ["tests::existing", "tests::other"]}"#,
+        "test tests::existing ... ok\ntest tests::other ... ok\n",
+        BUILD_FAILURE_LOG,
+        "test tests::existing ... ok\ntest tests::other ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let before_counts = report.debug_log_counts.iter().find(|counts| counts.label == "before").unwrap();
+    assert_eq!(before_counts.outcome, LogOutcome::BuildFailed);
+    let after_counts = report.debug_log_counts.iter().find(|counts| counts.label == "after").unwrap();
+    assert_eq!(after_counts.outcome, LogOutcome::Completed);
+
+    // The test patch uses new API, which is why before does not compile
+    assert!(!report.rule_checks[C9_BUILD_FAILED_IN_LOG].has_problem);
+    assert_eq!(report.verdict.decision, Decision::Accept);
+    // Rules that do not count missing tests still look at every log
+    for rule_id in [C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE, C5_DUPLICATES_IN_SAME_LOG_FOR_F2P_OR_P2P, C10_FLAKY_IN_ANY_LOG_PRESENT_IN_F2P_OR_P2P] {
+        assert!(report.rule_checks[rule_id].suppressed_by.is_none(), "{}", rule_id);
+    }
+}
+
+#[tokio::test]
+async fn build_failure_replaces_per_test_findings() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        r#"{"fail_to_pass": ["tests::new_feature"], "pass_to_pass": ["tests::existing", "tests::other"]}"#,
+        BUILD_FAILURE_LOG,
+        "test tests::existing ... ok\ntest tests::other ... ok\ntest tests::new_feature ... FAILED\n",
+        "test tests::existing ... ok\ntest tests::other ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let build = &report.rule_checks[C9_BUILD_FAILED_IN_LOG];
+    assert_eq!(build.examples, vec!["build failed in base.log (line 2: error[E0425]: cannot find value `parser` in this scope)"]);
+    assert_eq!(build.severity, Severity::Warning);
+    // Every P2P test is missing in base, which C4 would otherwise report
+    let c4 = &report.rule_checks[C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE];
+    assert!(!c4.has_problem);
+    assert_eq!(c4.suppressed_by.as_deref(), Some(C9_BUILD_FAILED_IN_LOG));
+    assert!(report.rule_checks[C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P].suppressed_by.is_none());
+    assert_eq!(report.verdict.reasons.len(), 1);
+    assert_eq!(report.verdict.reasons[0].rule_id, C9_BUILD_FAILED_IN_LOG);
+    assert_eq!(report.verdict.decision, Decision::NeedsManualReview);
+    // Suppressed rules never ran, so they do not raise the score as passes
+    let mut ran = report.rule_checks.clone();
+    ran.retain(|_, check| check.suppressed_by.is_none());
+    assert!(ran.len() < report.rule_checks.len());
+    assert_eq!(compute_score(&report.rule_checks), compute_score(&ran));
+}
+
+#[tokio::test]
+async fn p2p_missing_in_base_is_still_reported_when_before_fails_to_build() {
+    let dir = TempDir::new().unwrap();
+    let paths = write_deliverable(
+        &dir,
+        r#"{"fail_to_pass": ["tests::new_feature"], "pass_to_pass": ["tests::existing", "tests::other"]}"#,
+        "test tests::other ... ok\n",
+        BUILD_FAILURE_LOG,
+        "test tests::existing ... ok\ntest tests::other ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    // tests::existing needed before to pass, so the build failure there matters
+    assert!(report.rule_checks[C9_BUILD_FAILED_IN_LOG].has_problem);
+    let c4 = &report.rule_checks[C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE];
+    assert_eq!(c4.tests, vec!["tests::existing"]);
+    assert_eq!(report.verdict.decision, Decision::Reject);
+}
+
+#[tokio::test]
+async fn summary_lines_expose_an_under_parsed_log() {
+    let dir = TempDir::new().unwrap();
+    // The second result line is mangled by interleaved output, so the parser cannot see it
+    let before = "\
+running 2 tests
+test tests::existing ... ok
+test tests::new_feature ... [2024-01-01T00:00:00Z DEBUG] FAILED-ish noise
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n\ntest result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n",
+        before,
+        "test tests::existing ... ok\ntest tests::new_feature ... ok\n",
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let base = report.debug_log_counts.iter().find(|counts| counts.label == "base").unwrap();
+    assert_eq!(base.summary.as_ref().map(|summary| summary.passed), Some(1));
+    // No summary line in after, nothing to compare against
+    let after = report.debug_log_counts.iter().find(|counts| counts.label == "after").unwrap();
+    assert!(after.summary.is_none());
+    assert_eq!(report.parser_health.len(), 1, "{:?}", report.parser_health);
+    assert!(report.parser_health[0].starts_with("before.log: summary lines report 1 passed, 1 failed, 0 ignored"));
+}
+
+#[tokio::test]
+async fn summary_lines_count_each_binary_and_only_listed_skips() {
+    let dir = TempDir::new().unwrap();
+    // The same test name in two binaries: one entry in the sets, two in the summaries
+    let libtest = "\
+     Running unittests src/lib.rs (target/debug/deps/core-1a2b3c4d5e6f7a8b)
+
+running 2 tests
+test tests::existing ... ok
+test tests::new_feature ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+     Running unittests src/lib.rs (target/debug/deps/parser-0f1e2d3c4b5a6978)
+
+running 1 test
+test tests::existing ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+    // nextest leaves out SKIP lines at its default status level but still counts the skips
+    let nextest = "\
+    Starting 3 tests across 1 binary (1 test skipped)
+        PASS [   0.010s] my-crate tests::existing
+        PASS [   0.012s] my-crate tests::new_feature
+------------
+     Summary [   0.030s] 2 tests run: 2 passed, 1 skipped
+";
+    let paths = write_deliverable(&dir, MAIN_JSON, libtest, libtest, nextest);
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    let before = report.debug_log_counts.iter().find(|counts| counts.label == "before").unwrap();
+    assert_eq!(before.summary.as_ref().map(|summary| summary.passed), Some(3));
+    let after = report.debug_log_counts.iter().find(|counts| counts.label == "after").unwrap();
+    assert_eq!(after.summary.as_ref().map(|summary| summary.ignored), Some(1));
+    assert!(report.parser_health.is_empty(), "{:?}", report.parser_health);
+}
+
+#[tokio::test]
+async fn unlisted_surefire_methods_take_their_class_status() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["com.acme.ParserTest.testUnicode"], "pass_to_pass": ["com.acme.ParserTest.testEmpty", "com.acme.CacheTest.testPut"]}"#;
+    let failing = "\
+[INFO] Running com.acme.ParserTest
+[ERROR] Tests run: 2, Failures: 1, Errors: 0, Skipped: 0, Time elapsed: 0.05 s <<< FAILURE! -- in com.acme.ParserTest
+[ERROR] com.acme.ParserTest.testUnicode -- Time elapsed: 0.01 s <<< FAILURE!
+[INFO] Running com.acme.CacheTest
+[INFO] Tests run: 1, Failures: 0, Errors: 0, Skipped: 0, Time elapsed: 0.02 s -- in com.acme.CacheTest
+";
+    let passing = "\
+[INFO] Running com.acme.ParserTest
+[INFO] Tests run: 2, Failures: 0, Errors: 0, Skipped: 0, Time elapsed: 0.05 s -- in com.acme.ParserTest
+[INFO] Running com.acme.CacheTest
+[INFO] Tests run: 1, Failures: 0, Errors: 0, Skipped: 0, Time elapsed: 0.02 s -- in com.acme.CacheTest
+";
+    let paths = write_deliverable(&dir, main_json, failing, failing, passing);
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    // Surefire does not list passing methods, which is no sign of an under-parsed log
+    assert!(report.parser_health.is_empty(), "{:?}", report.parser_health);
+    assert_eq!(report.p2p_analysis["com.acme.ParserTest.testEmpty"].base, "passed");
+    assert_eq!(report.p2p_analysis["com.acme.CacheTest.testPut"].before, "passed");
+    assert_eq!(report.f2p_analysis["com.acme.ParserTest.testUnicode"].before, "failed");
+    assert_eq!(report.f2p_analysis["com.acme.ParserTest.testUnicode"].after, "passed");
+    assert!(!report.rule_checks[C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE].has_problem);
+    let resolution = report.name_resolutions.iter()
+        .find(|resolution| resolution.test == "com.acme.CacheTest.testPut" && resolution.log == "base")
+        .unwrap();
+    assert_eq!(resolution.strategy, "suite_status");
+    assert_eq!(resolution.matched.as_deref(), Some("com.acme.CacheTest"));
+}
+
+#[tokio::test]
+async fn binary_qualified_names_resolve_identical_test_names() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["parser tests::it_works"], "pass_to_pass": ["tests::it_works"]}"#;
+    let log = |parser_status: &str| format!("\
+     Running unittests src/lib.rs (target/debug/deps/core-1a2b3c4d5e6f7a8b)
+
+running 1 test
+test tests::it_works ... ok
+
+     Running unittests src/lib.rs (target/debug/deps/parser-0f1e2d3c4b5a6978)
+
+running 1 test
+test tests::it_works ... {}
+", parser_status);
+    let paths = write_deliverable(&dir, main_json, &log("ok"), &log("FAILED"), &log("ok"));
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    assert_eq!(report.f2p_analysis["parser tests::it_works"].before, "failed");
+    assert_eq!(report.f2p_analysis["parser tests::it_works"].after, "passed");
+    // The unqualified P2P name merges both binaries and is reported as a collision in every log
+    assert_eq!(report.p2p_analysis["tests::it_works"].before, "failed");
+    assert_eq!(report.binary_collisions.len(), 3);
+    let before = report.binary_collisions.iter().find(|collision| collision.log == "before").unwrap();
+    assert_eq!(before.test, "tests::it_works");
+    assert_eq!(before.binaries.get("core").map(String::as_str), Some("passed"));
+    assert_eq!(before.binaries.get("parser").map(String::as_str), Some("failed"));
+}
+
+#[tokio::test]
+async fn json_events_of_the_same_name_in_two_binaries_are_reported_as_a_collision() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["parser tests::it_works"], "pass_to_pass": ["tests::it_works"]}"#;
+    let log = |parser_event: &str| format!(r#"{{"type":"test","event":"ok","name":"core$tests::it_works"}}
+{{"type":"test","event":"{}","name":"parser$tests::it_works"}}
+"#, parser_event);
+    let paths = write_deliverable(&dir, main_json, &log("ok"), &log("failed"), &log("ok"));
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    assert_eq!(report.f2p_analysis["parser tests::it_works"].before, "failed");
+    assert_eq!(report.f2p_analysis["parser tests::it_works"].after, "passed");
+    let before = report.binary_collisions.iter().find(|collision| collision.log == "before").unwrap();
+    assert_eq!(before.test, "tests::it_works");
+    assert_eq!(before.binaries.get("core").map(String::as_str), Some("passed"));
+    assert_eq!(before.binaries.get("parser").map(String::as_str), Some("failed"));
+}
+
+#[tokio::test]
+async fn nextest_ids_map_onto_main_json_names() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{
+        "repo": "acme/widgets",
+        "fail_to_pass": ["widgets-core kernel::new_feature"],
+        "pass_to_pass": ["assert::existing", "widgets_cli::args::defaults", "Widgets/legacy_check"]
+    }"#;
+    let log = |new_feature: &str| format!("\
+        PASS [   0.010s] widgets::lib assert::existing
+        PASS [   0.010s] widgets-cli args::defaults
+        PASS [   0.010s] widgets-core legacy_check
+        {} [   0.020s] widgets-core kernel::new_feature
+", new_feature);
+    let paths = write_deliverable(&dir, main_json, &log("FAIL"), &log("FAIL"), &log("PASS"));
+    let mapping = NameMapping { pattern: r"^widgets-core (\w+)$".to_string(), replacement: "Widgets/$1".to_string() };
+    let options = AnalysisOptions {
+        name_mappings: Some(std::collections::BTreeMap::from([("acme/widgets".to_string(), vec![mapping])])),
+        trace: true,
+        ..Default::default()
+    };
+
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+
+    assert_eq!(report.f2p_analysis["widgets-core kernel::new_feature"].before, "failed");
+    assert_eq!(report.f2p_analysis["widgets-core kernel::new_feature"].after, "passed");
+    for name in ["assert::existing", "widgets_cli::args::defaults", "Widgets/legacy_check"] {
+        assert_eq!(report.p2p_analysis[name].base, "passed", "{}", name);
+    }
+    let trace = report.trace.unwrap();
+    assert_eq!(trace["widgets_cli::args::defaults"][0].decision, "no exact match; resolved to widgets-cli args::defaults by crate_qualified (confidence 0.90)");
+    assert_eq!(trace["Widgets/legacy_check"][0].decision, "no exact match; resolved to widgets-core legacy_check by name_mapping (confidence 1.00)");
+}
+
+#[tokio::test]
+async fn differently_written_names_are_reconciled_or_left_with_candidates() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{
+        "fail_to_pass": ["src/lib.rs - Parser::parse (line 12)"],
+        "pass_to_pass": ["tests::existing", "tests.spacing", "src/lib.rs - Lexer::new (line 3)", "tests::spacing_edge"]
+    }"#;
+    let log = "\
+test  tests::existing ... ok
+test tests::spacing ... ok
+test src/lib.rs - Lexer::new (line 5) ... ok
+test src/lib.rs - Lexer::new (line 30) ... ok
+test src/lib.rs - Parser::parse (line 15) ... ok
+";
+    let paths = write_deliverable(&dir, main_json, log, log, log);
+    let options = AnalysisOptions { trace: true, ..Default::default() };
+
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+
+    let resolution = |test: &str| report.name_resolutions.iter()
+        .find(|resolution| resolution.test == test && resolution.log == "after")
+        .unwrap();
+    assert_eq!(report.f2p_analysis["src/lib.rs - Parser::parse (line 12)"].after, "passed");
+    assert_eq!(resolution("src/lib.rs - Parser::parse (line 12)").strategy, "doctest");
+    assert!(report.f2p_analysis["src/lib.rs - Parser::parse (line 12)"].doctest);
+    assert!(!report.p2p_analysis["tests::existing"].doctest);
+    assert_eq!(resolution("tests.spacing").matched.as_deref(), Some("tests::spacing"));
+    assert_eq!(resolution("tests.spacing").strategy, "separator");
+    assert_eq!(resolution("tests.spacing").confidence, 0.7);
+    // Two examples on the same item, neither more likely than the other
+    assert_eq!(report.p2p_analysis["src/lib.rs - Lexer::new (line 3)"].after, "missing");
+    assert!(resolution("src/lib.rs - Lexer::new (line 3)").matched.is_none());
+    assert_eq!(resolution("src/lib.rs - Lexer::new (line 3)").candidates.len(), 2);
+    // A name that only overlaps another stays missing, with the overlap kept as a candidate
+    assert_eq!(report.p2p_analysis["tests::spacing_edge"].after, "missing");
+    assert!(resolution("tests::spacing_edge").matched.is_none());
+    assert_eq!(resolution("tests::spacing_edge").strategy, "partial");
+    assert_eq!(resolution("tests::spacing_edge").candidates, vec!["tests::spacing"]);
+    let trace = report.trace.as_ref().unwrap();
+    assert_eq!(trace["tests::spacing_edge"][0].decision, r#"no exact match; partial name matches: ["tests::spacing"]"#);
+}
+
+#[tokio::test]
+async fn flaky_validation_tests_are_a_rejection_reason() {
+    let dir = TempDir::new().unwrap();
+    let after = "\
+        PASS [   0.010s] my-crate tests::existing
+   TRY 1 FAIL [   0.020s] my-crate tests::new_feature
+  RETRY 2/2 [         ] my-crate tests::new_feature
+   TRY 2 PASS [   0.015s] my-crate tests::new_feature
+";
+    let paths = write_deliverable(
+        &dir,
+        MAIN_JSON,
+        "test tests::existing ... ok\n",
+        "test tests::existing ... ok\ntest tests::new_feature ... FAILED\n",
+        after,
+    );
+
+    let report = analyze_logs(paths).await.unwrap();
+
+    assert_eq!(report.f2p_analysis["tests::new_feature"].after, "passed");
+    let flaky = &report.rule_checks[C10_FLAKY_IN_ANY_LOG_PRESENT_IN_F2P_OR_P2P];
+    assert!(flaky.has_problem);
+    assert_eq!(flaky.examples, vec!["tests::new_feature (flaky in after: failed then passed)"]);
+    assert_eq!(report.verdict.decision, Decision::Reject);
+}
+
+#[tokio::test]
+async fn binary_qualified_names_keep_their_failures_retries_and_durations() {
+    let dir = TempDir::new().unwrap();
+    let main_json = r#"{"fail_to_pass": ["my-crate tests::new_feature"], "pass_to_pass": ["my-crate tests::existing"]}"#;
+    let before = "\
+        PASS [   0.010s] my-crate tests::existing
+        FAIL [   0.020s] my-crate tests::new_feature
+
+--- STDOUT:              my-crate tests::new_feature ---
+thread 'tests::new_feature' panicked at tests/common.rs:8:43:
+missing fixture tests/fixtures/new_feature.json
+";
+    let after = "\
+        PASS [   0.010s] my-crate tests::existing
+   TRY 1 FAIL [  45.000s] my-crate tests::new_feature
+  RETRY 2/2 [         ] my-crate tests::new_feature
+   TRY 2 PASS [  40.000s] my-crate tests::new_feature
+";
+    let paths = write_deliverable(&dir, main_json, "        PASS [   0.010s] my-crate tests::existing\n", before, after);
+    let options = AnalysisOptions { slow_test_threshold: Some(30.0), ..Default::default() };
+
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+
+    let row = &report.f2p_analysis["my-crate tests::new_feature"];
+    assert_eq!(row.failures["before"].kind, FailureKind::Infrastructure);
+    assert!(report.rule_checks[C8_F2P_FAILING_IN_BEFORE_FOR_WRONG_REASON].has_problem);
+    let flaky = &report.rule_checks[C10_FLAKY_IN_ANY_LOG_PRESENT_IN_F2P_OR_P2P];
+    assert_eq!(flaky.examples, vec!["my-crate tests::new_feature (flaky in after: failed then passed)"]);
+    assert!(report.rule_checks[C11_F2P_OR_P2P_SLOWER_THAN_THRESHOLD].has_problem);
+    assert_eq!(report.slow_tests[0].test, "my-crate tests::new_feature");
+    assert_eq!(report.slow_tests[0].before, Some(0.02));
+}
+
+#[tokio::test]
+async fn slow_tests_are_listed_and_flagged_over_the_threshold() {
+    let dir = TempDir::new().unwrap();
+    let log = |new_feature: &str, seconds: &str| format!("\
+        PASS [   0.010s] my-crate tests::existing
+        {} [ {}s] my-crate tests::new_feature
+", new_feature, seconds);
+    let paths = write_deliverable(&dir, MAIN_JSON, &log("PASS", "0.500"), &log("FAIL", "0.400"), &log("PASS", "95.000"));
+    let options = AnalysisOptions { slow_test_threshold: Some(30.0), ..Default::default() };
+
+    let report = analyze_logs_with_options(paths, options).await.unwrap();
+
+    assert_eq!(report.slow_tests.len(), 2);
+    let slowest = &report.slow_tests[0];
+    assert_eq!((slowest.test.as_str(), slowest.kind.as_str()), ("tests::new_feature", "f2p"));
+    assert_eq!((slowest.before, slowest.after), (Some(0.4), Some(95.0)));
+    assert!(slowest.over_threshold);
+    assert!(!report.slow_tests[1].over_threshold);
+    let slow = &report.rule_checks[C11_F2P_OR_P2P_SLOWER_THAN_THRESHOLD];
+    assert_eq!(slow.examples, vec!["tests::new_feature (after: 95.0s, threshold 30s)"]);
+}
@@ -0,0 +1,73 @@
+use crate::analysis::{ParsedLog, C6_TEST_MARKED_FAILED_IN_REPORT_BUT_PASSING_IN_AGENT, DEFAULT_SLOW_TEST_THRESHOLD_SECS};
+use crate::rules::{AnalysisContext, ReportAgentMismatch, Rule, RuleRegistry};
+use std::collections::HashMap;
+
+fn statuses(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(name, status)| (name.to_string(), status.to_string())).collect()
+}
+
+fn context<'a>(
+    f2p: &'a [String],
+    p2p: &'a [String],
+    empty: &'a ParsedLog,
+    agent: Option<&'a ParsedLog>,
+    agent_status: HashMap<String, String>,
+    report_data: Option<&'a serde_json::Value>,
+) -> AnalysisContext<'a> {
+    AnalysisContext {
+        fail_to_pass: f2p,
+        pass_to_pass: p2p,
+        universe: p2p.iter().chain(f2p.iter()).cloned().collect(),
+        base_parsed: empty,
+        before_parsed: empty,
+        after_parsed: empty,
+        agent_parsed: agent,
+        base_status: HashMap::new(),
+        before_status: HashMap::new(),
+        after_status: HashMap::new(),
+        agent_status,
+        report_status: HashMap::new(),
+        log_names: HashMap::new(),
+        base_path: "",
+        before_path: "",
+        after_path: "",
+        agent_path: None,
+        report_data,
+        file_paths: &[],
+        slow_test_threshold: DEFAULT_SLOW_TEST_THRESHOLD_SECS,
+    }
+}
+
+#[test]
+fn c6_flags_failed_in_report_but_passing_in_agent() {
+    let f2p = vec!["tests::fixed".to_string()];
+    let p2p = vec!["tests::stable".to_string()];
+    let empty = ParsedLog::default();
+    let report = serde_json::json!({
+        "instance-1": {
+            "tests_status": {
+                "FAIL_TO_PASS": { "success": [], "failure": ["tests::fixed"] },
+                "PASS_TO_PASS": { "success": ["tests::stable"], "failure": [] }
+            }
+        }
+    });
+    let agent_status = statuses(&[("tests::fixed", "passed"), ("tests::stable", "passed")]);
+    let ctx = context(&f2p, &p2p, &empty, Some(&empty), agent_status, Some(&report));
+
+    let outcome = ReportAgentMismatch.evaluate(&ctx);
+
+    assert!(outcome.has_problem());
+    assert_eq!(outcome.examples, vec!["tests::fixed (marked as failed in report.json but passing in agent log)"]);
+}
+
+#[test]
+fn c6_is_skipped_without_agent_log() {
+    let f2p = vec!["tests::fixed".to_string()];
+    let empty = ParsedLog::default();
+    let report = serde_json::json!({ "tests::fixed": "failed" });
+    let ctx = context(&f2p, &[], &empty, None, HashMap::new(), Some(&report));
+
+    let checks = RuleRegistry::with_default_rules().evaluate(&ctx, &Default::default());
+
+    assert!(!checks[C6_TEST_MARKED_FAILED_IN_REPORT_BUT_PASSING_IN_AGENT].has_problem);
+}
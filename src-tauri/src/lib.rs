@@ -4,6 +4,9 @@ mod drive;
 mod settings;
 pub mod report_checker;
 pub mod analysis;
+pub mod parsers;
+pub mod rules;
+pub mod names;
 
 #[cfg(test)]
 mod analysis_test;
@@ -11,10 +14,15 @@ mod analysis_test;
 #[cfg(test)]
 mod c6_test;
 
+#[cfg(test)]
+mod parsers_test;
+
 // Re-export commonly used types
 pub use auth::GoogleTokens;
-pub use report_checker::{FileInfo, ValidationResult, DownloadResult};
-pub use analysis::{AnalysisResult, TestLists, LogSearchResults};
+pub use report_checker::{FileInfo, ValidationResult, DownloadResult, ProcessingResult};
+pub use analysis::{AnalysisResult, AnalysisReport, Decision, DeliverableFiles, Verdict, TestLists, LogSearchResults};
+pub use rules::RuleConfig;
+pub use names::NameMapping;
 
 // Tauri command entry points - Authentication
 #[tauri::command]
@@ -59,6 +67,26 @@ fn load_setting(key: String) -> Result<String, String> {
     settings::load_setting(key)
 }
 
+#[tauri::command]
+fn get_rule_configs() -> Result<std::collections::BTreeMap<String, RuleConfig>, String> {
+    rules::effective_rule_configs()
+}
+
+#[tauri::command]
+fn save_rule_configs(configs: std::collections::BTreeMap<String, RuleConfig>) -> Result<(), String> {
+    rules::save_rule_configs(configs)
+}
+
+#[tauri::command]
+fn get_name_mappings() -> Result<std::collections::BTreeMap<String, Vec<NameMapping>>, String> {
+    names::load_name_mappings()
+}
+
+#[tauri::command]
+fn save_name_mappings(mappings: std::collections::BTreeMap<String, Vec<NameMapping>>) -> Result<(), String> {
+    names::save_name_mappings(mappings)
+}
+
 #[tauri::command]
 fn debug_settings() -> Result<String, String> {
     settings::debug_settings()
@@ -86,7 +114,7 @@ async fn download_deliverable(files_to_download: Vec<FileInfo>, folder_id: Strin
 }
 
 #[tauri::command]
-async fn process_deliverable(downloaded_files: Vec<FileInfo>) -> Result<serde_json::Value, String> {
+async fn process_deliverable(downloaded_files: Vec<FileInfo>) -> Result<ProcessingResult, String> {
     report_checker::process_deliverable(downloaded_files).await
 }
 
@@ -117,8 +145,22 @@ fn search_logs(file_paths: Vec<String>, test_name: String) -> Result<LogSearchRe
 }
 
 #[tauri::command]
-async fn analyze_logs(file_paths: Vec<String>) -> Result<serde_json::Value, String> {
-    analysis::analyze_logs(file_paths).await
+async fn analyze_logs(
+    file_paths: Vec<String>,
+    trace: Option<bool>,
+    parser_overrides: Option<std::collections::BTreeMap<String, String>>,
+) -> Result<AnalysisReport, String> {
+    let options = analysis::AnalysisOptions {
+        trace: trace.unwrap_or(false),
+        parser_overrides: parser_overrides.unwrap_or_default(),
+        ..Default::default()
+    };
+    analysis::analyze_logs_with_options(file_paths, options).await
+}
+
+#[tauri::command]
+fn get_log_parsers() -> Vec<String> {
+    parsers::ParserRegistry::with_default_parsers().parsers().map(|parser| parser.name().to_string()).collect()
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -143,7 +185,12 @@ pub fn run() {
             get_test_lists,
             search_logs,
             analyze_logs,
+            get_log_parsers,
             debug_settings,
+            get_rule_configs,
+            save_rule_configs,
+            get_name_mappings,
+            save_name_mappings,
             get_temp_dir_size,
             clear_temp_dir
         ])
@@ -0,0 +1,324 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use lazy_static::lazy_static;
+use crate::analysis::{parse_doctest_name, strip_ansi_color_codes, ParsedLog};
+use crate::settings::{load_setting, save_setting};
+
+lazy_static! {
+    // Doctest names end in the line of the example, which moves whenever the file is edited
+    static ref DOCTEST_LINE_RE: Regex = Regex::new(r"\s*\(line \d+\)\s*$").unwrap();
+}
+
+/// Settings key holding the per-repository name mappings as a JSON object keyed by repository
+/// (`owner/name` as in main.json's `repo`), with `*` for mappings that apply to every repository
+pub const NAME_MAPPING_SETTING: &str = "name_mappings";
+
+/// Key of the mappings that apply to every repository
+pub const ANY_REPO: &str = "*";
+
+/// Rewrites the id a log reports for a test into the name main.json uses. The pattern is
+/// matched against `binary-id test::path` for runners that name the binary, otherwise against
+/// the test name alone; the replacement may refer to capture groups as `$1` or `${name}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NameMapping {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+pub fn load_name_mappings() -> Result<BTreeMap<String, Vec<NameMapping>>, String> {
+    let raw = load_setting(NAME_MAPPING_SETTING.to_string())?;
+    if raw.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    serde_json::from_str(&raw)
+        .map_err(|e| format!("Failed to parse {} setting: {}", NAME_MAPPING_SETTING, e))
+}
+
+pub fn save_name_mappings(mappings: BTreeMap<String, Vec<NameMapping>>) -> Result<(), String> {
+    for (repo, rules) in &mappings {
+        for rule in rules {
+            Regex::new(&rule.pattern)
+                .map_err(|e| format!("Invalid name mapping pattern '{}' for {}: {}", rule.pattern, repo, e))?;
+        }
+    }
+    let raw = serde_json::to_string(&mappings)
+        .map_err(|e| format!("Failed to serialize name mappings: {}", e))?;
+    save_setting(NAME_MAPPING_SETTING.to_string(), raw)
+}
+
+/// Mappings that apply to `repo`: its own followed by the ones for every repository
+pub fn mappings_for_repo(mappings: &BTreeMap<String, Vec<NameMapping>>, repo: Option<&str>) -> Vec<NameMapping> {
+    repo.and_then(|repo| mappings.get(repo))
+        .into_iter()
+        .chain(mappings.get(ANY_REPO))
+        .flatten()
+        .cloned()
+        .collect()
+}
+
+/// Compiled name mappings for one analysis run
+#[derive(Debug, Default)]
+pub struct NameMapper {
+    rules: Vec<(Regex, String)>,
+}
+
+impl NameMapper {
+    pub fn new(mappings: &[NameMapping]) -> Self {
+        let rules = mappings.iter().filter_map(|mapping| match Regex::new(&mapping.pattern) {
+            Ok(regex) => Some((regex, mapping.replacement.clone())),
+            Err(e) => {
+                println!("Skipping name mapping '{}': {}", mapping.pattern, e);
+                None
+            }
+        }).collect();
+        NameMapper { rules }
+    }
+
+    /// Status, log id and log test name of every name the mappings turn a log id into, a
+    /// failure winning when several ids map to the same name
+    pub fn map_log<'a>(&self, parsed: &'a ParsedLog) -> HashMap<String, (String, String, &'a String)> {
+        let mut out: HashMap<String, (String, String, &String)> = HashMap::new();
+        if self.rules.is_empty() {
+            return out;
+        }
+        for (id, test, status) in log_ids(parsed) {
+            for (regex, replacement) in &self.rules {
+                if !regex.is_match(&id) {
+                    continue;
+                }
+                let mapped = regex.replace(&id, replacement.as_str()).to_string();
+                let entry = out.entry(mapped).or_insert_with(|| (status.to_string(), id.clone(), test));
+                if status == "failed" {
+                    *entry = (status.to_string(), id.clone(), test);
+                }
+                break;
+            }
+        }
+        out
+    }
+}
+
+// Every result of a log as the runner identified it, `binary-id test::path` where the binary is
+// known, with the test name the log is keyed by
+fn log_ids(parsed: &ParsedLog) -> Vec<(String, &String, &str)> {
+    let mut ids = Vec::new();
+    for test in &parsed.all {
+        match parsed.binaries.get(test) {
+            Some(per_binary) => {
+                ids.extend(per_binary.iter().map(|(binary, status)| (format!("{} {}", binary, test), test, status.as_str())));
+            }
+            None => ids.push((test.clone(), test, merged_status(parsed, test))),
+        }
+    }
+    ids
+}
+
+// Same precedence as status_lookup: failed, then passed, then ignored
+fn merged_status(parsed: &ParsedLog, test: &str) -> &'static str {
+    if parsed.failed.contains(test) {
+        "failed"
+    } else if parsed.passed.contains(test) {
+        "passed"
+    } else {
+        "ignored"
+    }
+}
+
+/// How a main.json name that a log does not report verbatim was matched. Unresolved names that
+/// several log names fit equally well, or that only overlap log names (`partial`), keep
+/// `matched` empty and list those names as `candidates`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NameResolution {
+    pub test: String,
+    pub log: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matched: Option<String>,
+    pub strategy: String,
+    pub confidence: f32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<String>,
+}
+
+// Normalizations for fuzzy matching, each applied on top of the previous ones, with the
+// confidence of a unique match at that step
+const FUZZY_STRATEGIES: [(&str, f32); 5] = [
+    ("whitespace", 0.95),
+    ("ansi", 0.95),
+    ("doctest_line", 0.8),
+    ("separator", 0.7),
+    ("binary_prefix", 0.6),
+];
+
+fn normalize_step(name: &str, step: usize) -> String {
+    match FUZZY_STRATEGIES[step].0 {
+        "whitespace" => name.split_whitespace().collect::<Vec<_>>().join(" "),
+        "ansi" => strip_ansi_color_codes(name),
+        "doctest_line" => DOCTEST_LINE_RE.replace(name, "").to_string(),
+        "separator" => name.replace('.', "::"),
+        _ => match name.split_once(' ') {
+            Some((_, rest)) if !rest.contains(' ') => rest.to_string(),
+            _ => name.to_string(),
+        },
+    }
+}
+
+/// Result of resolving a main.json name against one log
+pub(crate) struct Resolved {
+    /// `None` while the name stays ambiguous
+    pub(crate) status: Option<String>,
+    /// Name the log keys its per-test data by (failures, durations, retries), when a single
+    /// test of the log matched
+    pub(crate) log_name: Option<String>,
+    pub(crate) resolution: NameResolution,
+}
+
+/// Names one log reports, indexed for resolving main.json names that do not match verbatim
+pub(crate) struct LogNames<'a> {
+    label: &'a str,
+    parsed: &'a ParsedLog,
+    mapped: HashMap<String, (String, String, &'a String)>,
+    // Per fuzzy strategy, log names by their normalized form
+    fuzzy: Vec<HashMap<String, Vec<&'a String>>>,
+}
+
+impl<'a> LogNames<'a> {
+    pub(crate) fn new(label: &'a str, parsed: &'a ParsedLog, mapper: &NameMapper) -> Self {
+        let mut keys: Vec<(&String, String)> = parsed.all.iter().map(|name| (name, name.clone())).collect();
+        let fuzzy = (0..FUZZY_STRATEGIES.len()).map(|step| {
+            let mut index: HashMap<String, Vec<&String>> = HashMap::new();
+            for (name, key) in keys.iter_mut() {
+                *key = normalize_step(key, step);
+                index.entry(key.clone()).or_default().push(*name);
+            }
+            index
+        }).collect();
+        LogNames { label, parsed, mapped: mapper.map_log(parsed), fuzzy }
+    }
+
+    /// Status of a main.json name the log does not report verbatim, with how it was resolved.
+    /// Tried in order: the name without a leading binary id (`foo tests::it_works`), the name
+    /// qualified by a crate or binary (`foo::tests::it_works`), the configured mappings, a doctest
+    /// on the same file and item at another line, the totals of the class a `Class.method` test
+    /// belongs to, the fuzzy normalizations, then log names that merely overlap this one. The status
+/// is `None` when the name stays ambiguous, which overlapping names always do.
+    pub(crate) fn resolve(&self, name: &str) -> Option<Resolved> {
+        let parsed = self.parsed;
+        let resolution = |matched: &str, strategy: &str, confidence: f32| NameResolution {
+            test: name.to_string(),
+            log: self.label.to_string(),
+            matched: Some(matched.to_string()),
+            strategy: strategy.to_string(),
+            confidence,
+            candidates: Vec::new(),
+        };
+        let found = |status: String, log_name: Option<&str>, resolution: NameResolution| Some(Resolved {
+            status: Some(status),
+            log_name: log_name.map(str::to_string),
+            resolution,
+        });
+        let ambiguous = |candidates: Vec<String>, strategy: &str, confidence: f32| Some(Resolved {
+            status: None,
+            log_name: None,
+            resolution: NameResolution { matched: None, candidates, ..resolution(name, strategy, confidence) },
+        });
+        if let Some((qualifier, test)) = name.split_once(' ') {
+            let test = test.trim();
+            if let Some((binary, status)) = binary_status(parsed, qualifier, test) {
+                return found(status, Some(test), resolution(&format!("{} {}", binary, test), "binary_stripped", 0.95));
+            }
+            if parsed.all.contains(test) && !parsed.binaries.contains_key(test) {
+                return found(merged_status(parsed, test).to_string(), Some(test), resolution(test, "binary_stripped", 0.9));
+            }
+        }
+        for (i, _) in name.match_indices("::") {
+            let test = &name[i + 2..];
+            if let Some((binary, status)) = binary_status(parsed, &name[..i], test) {
+                return found(status, Some(test), resolution(&format!("{} {}", binary, test), "crate_qualified", 0.9));
+            }
+        }
+        if let Some((status, id, test)) = self.mapped.get(name) {
+            return found(status.clone(), Some(test.as_str()), resolution(id, "name_mapping", 1.0));
+        }
+        if let Some(wanted) = parse_doctest_name(name) {
+            let mut examples: Vec<&String> = parsed.doctests.iter()
+                .filter(|(_, doctest)| doctest.same_example(&wanted))
+                .map(|(test, _)| test)
+                .collect();
+            match examples.as_slice() {
+                [] => {}
+                [only] => return found(merged_status(parsed, only).to_string(), Some(only.as_str()), resolution(only, "doctest", 0.95)),
+                _ => {
+                    examples.sort();
+                    return ambiguous(examples.into_iter().cloned().collect(), "doctest", 0.95);
+                }
+            }
+        }
+        if let Some((class, _)) = name.rsplit_once('.') {
+            if let Some(suite) = parsed.suites.iter().find(|suite| suite.name.as_deref() == Some(class)) {
+                let listed_failures = parsed.failed.iter()
+                    .filter(|test| test.rsplit_once('.').is_some_and(|(test_class, _)| test_class == class))
+                    .count();
+                let status = if suite.failed == 0 && suite.ignored == 0 {
+                    Some("passed")
+                } else if suite.passed == 0 && suite.failed == 0 {
+                    Some("ignored")
+                } else if suite.ignored == 0 && listed_failures >= suite.failed {
+                    // Every failure of the class is listed by name, so this one passed
+                    Some("passed")
+                } else {
+                    None
+                };
+                // The class has no per-test data of this method
+                return match status {
+                    Some(status) => found(status.to_string(), None, resolution(class, "suite_status", 0.8)),
+                    None => ambiguous(vec![class.to_string()], "suite_status", 0.8),
+                };
+            }
+        }
+
+        let mut key = name.to_string();
+        for (step, index) in self.fuzzy.iter().enumerate() {
+            key = normalize_step(&key, step);
+            let (strategy, confidence) = FUZZY_STRATEGIES[step];
+            match index.get(&key).map(Vec::as_slice) {
+                Some([only]) => {
+                    return found(merged_status(parsed, only).to_string(), Some(only.as_str()), resolution(only, strategy, confidence));
+                }
+                Some(candidates) => {
+                    let mut candidates: Vec<String> = candidates.iter().map(|candidate| candidate.to_string()).collect();
+                    candidates.sort();
+                    return ambiguous(candidates, strategy, confidence);
+                }
+                None => {}
+            }
+        }
+
+        // Names containing or contained in this one are too loose to match, but worth listing
+        let mut partial: Vec<String> = parsed.all.iter()
+            .filter(|test| test.contains(name) || name.contains(test.as_str()))
+            .cloned()
+            .collect();
+        if partial.is_empty() {
+            return None;
+        }
+        partial.sort();
+        ambiguous(partial, "partial", 0.5)
+    }
+}
+
+fn binary_status(parsed: &ParsedLog, qualifier: &str, test: &str) -> Option<(String, String)> {
+    parsed.binaries.get(test)?
+        .iter()
+        .find(|(binary, _)| binary_matches(qualifier, binary))
+        .map(|(binary, status)| (binary.clone(), status.clone()))
+}
+
+// libtest names binaries after their target (`foo_bar`, `integration`), nextest by binary id
+// (`foo-bar`, `foo-bar::integration`, `foo-bar::bin/cli`), so compare on the target part too
+fn binary_matches(qualifier: &str, binary: &str) -> bool {
+    let normalize = |s: &str| s.trim().replace('-', "_");
+    let target = |s: &str| s.rsplit("::").next().unwrap_or(s).rsplit('/').next().unwrap_or(s).to_string();
+    let (qualifier, binary) = (normalize(qualifier), normalize(binary));
+    qualifier == binary || target(&qualifier) == binary || qualifier == target(&binary)
+}
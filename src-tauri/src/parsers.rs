@@ -0,0 +1,944 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::BTreeMap;
+use crate::analysis::{
+    detect_log_outcome, failure_detail_from_output, libtest_confidence, line_at, nextest_confidence, parse_doctest_name, parse_libtest_log, parse_nextest_log, parse_rust_log_single_line,
+    record_binary_status, strip_ansi_color_codes, summary_line_totals, ui_test_confidence, FailureKind, ParsedLog, SuiteSummary, TraceRecorder,
+};
+
+lazy_static! {
+    // pytest -v: "tests/test_x.py::TestA::test_b[1-2] PASSED   [ 10%]", skips may carry "(reason)"
+    static ref PYTEST_VERBOSE_RE: Regex = Regex::new(r"^(\S+::\S.*?)\s+(PASSED|FAILED|ERROR|SKIPPED|XFAIL|XPASS)(?:\s+\(.*\))?(?:\s+\[\s*\d+%\])?$").unwrap();
+    // -rA short summary: "FAILED tests/test_x.py::test_c - AssertionError", optionally behind an xdist "[gw0] [ 10%]" prefix
+    static ref PYTEST_SUMMARY_RE: Regex = Regex::new(r"^(?:\[gw\d+\]\s+\[\s*\d+%\]\s+)?(PASSED|FAILED|ERROR|SKIPPED|XFAIL|XPASS)\s+(\S+::.+)$").unwrap();
+    static ref PYTEST_SESSION_RE: Regex = Regex::new(r"^=+ test session starts =+$").unwrap();
+
+    // go test -v: "=== RUN   TestX/sub_case" and "    --- PASS: TestX/sub_case (0.00s)"
+    static ref GO_RUN_RE: Regex = Regex::new(r"^=== RUN\s+(\S+)$").unwrap();
+    static ref GO_RESULT_RE: Regex = Regex::new(r"^--- (PASS|FAIL|SKIP): (\S+) \(([\d.]+)s\)").unwrap();
+    // Package summary: "ok  	github.com/org/repo/pkg	0.012s" or "FAIL	github.com/org/repo/pkg [build failed]"
+    static ref GO_PACKAGE_RE: Regex = Regex::new(r"^(ok|FAIL)\s+(\S+)\s+(?:[\d.]+s|\(cached\)|\[.+\])").unwrap();
+
+    // JavaScript runners, matched on trimmed lines: "✓ renders header (5 ms)", "✕ …", "○ skipped …"
+    static ref JS_RESULT_RE: Regex = Regex::new(r"^(✓|✔|√|✕|✖|×|○|↓|✎ todo)\s+(.+)$").unwrap();
+    // Trailing "(5 ms)" (Jest, Mocha); Vitest prints a bare "12ms", which elsewhere may end the title
+    static ref JS_DURATION_RE: Regex = Regex::new(r"\s+\((\d+(?:\.\d+)?)\s*(m?s)\)$").unwrap();
+    static ref VITEST_DURATION_RE: Regex = Regex::new(r"\s+\(?(\d+(?:\.\d+)?)\s*(m?s)\)?$").unwrap();
+    // Vitest suite and file lines carry a test count: "✓ src/a.test.ts (3 tests)"
+    static ref VITEST_COUNT_RE: Regex = Regex::new(r"\s+\(\d+(?:\s+tests?)?\)$").unwrap();
+    static ref JS_FILE_TITLE_RE: Regex = Regex::new(r"^\S+\.[cm]?[jt]sx?$").unwrap();
+    static ref JEST_FILE_RE: Regex = Regex::new(r"^(PASS|FAIL)\s+\S+\.[cm]?[jt]sx?\b").unwrap();
+    static ref JEST_SUMMARY_RE: Regex = Regex::new(r"^Tests:\s+.*\d+ total").unwrap();
+    static ref MOCHA_FAILED_RE: Regex = Regex::new(r"^\d+\) (.+)$").unwrap();
+    static ref MOCHA_PENDING_RE: Regex = Regex::new(r"^- (.+)$").unwrap();
+    static ref MOCHA_SUMMARY_RE: Regex = Regex::new(r"^\d+ (passing|failing|pending)\b").unwrap();
+    static ref VITEST_BANNER_RE: Regex = Regex::new(r"^RUN\s+v\d+\.\d+").unwrap();
+    static ref VITEST_SUMMARY_RE: Regex = Regex::new(r"^Test Files\s+\d+").unwrap();
+
+    // JUnit XML: self-closing "<testcase .../>" passes, otherwise the body holds failure/error/skipped
+    static ref JUNIT_TESTCASE_RE: Regex = Regex::new(r"(?s)<testcase\b([^>]*?)(?:/>|>(.*?)</testcase>)").unwrap();
+    static ref JUNIT_NAME_RE: Regex = Regex::new(r#"(?:^|\s)name\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref JUNIT_CLASSNAME_RE: Regex = Regex::new(r#"(?:^|\s)classname\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref JUNIT_TIME_RE: Regex = Regex::new(r#"(?:^|\s)time\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref JUNIT_FAILED_RE: Regex = Regex::new(r"<(?:failure|error)\b").unwrap();
+    static ref JUNIT_SKIPPED_RE: Regex = Regex::new(r"<skipped\b").unwrap();
+    // pytest names its suite "pytest" unless `junit_suite_name` is set
+    static ref JUNIT_PYTEST_SUITE_RE: Regex = Regex::new(r#"<testsuite\b[^>]*\sname\s*=\s*["']pytest["']"#).unwrap();
+    // A Java class: dotted lowercase packages ending in a capitalized class, "com.acme.FooTest"
+    static ref JAVA_CLASSNAME_RE: Regex = Regex::new(r"^(?:[a-z_][\w$]*\.)*[A-Z][\w$]*$").unwrap();
+    static ref JUNIT_LINE_RE: Regex = Regex::new(r"^<testcase\b.*\sname\s*=").unwrap();
+
+    // Maven Surefire, behind an optional "[INFO] " level: "Running com.foo.BarTest",
+    // "Tests run: 3, Failures: 1, Errors: 0, Skipped: 0, Time elapsed: 0.05 s <<< FAILURE! - in com.foo.BarTest"
+    // (Surefire 3 separates the class with "-- in")
+    static ref SUREFIRE_RUNNING_RE: Regex = Regex::new(r"^(?:\[\w+\]\s+)?Running ([\w.$]+)$").unwrap();
+    static ref SUREFIRE_CLASS_RE: Regex = Regex::new(r"^(?:\[\w+\]\s+)?Tests run: (\d+), Failures: (\d+), Errors: (\d+), Skipped: (\d+)(?:, Time elapsed: ([\d.,]+) s(?:ec)?)?.*? --? in ([\w.$]+)").unwrap();
+    // Per-method lines, "testBaz(com.foo.BarTest)  Time elapsed" (Surefire 2) or
+    // "com.foo.BarTest.testBaz -- Time elapsed" (Surefire 3)
+    static ref SUREFIRE_METHOD_RE: Regex = Regex::new(r"^(?:\[\w+\]\s+)?(\S+?)\s+(?:--\s+)?Time elapsed: ([\d.,]+) s(?:ec)?(?:\s+<<< (FAILURE|ERROR|SKIPPED)!)?").unwrap();
+    // End-of-run recap under "[ERROR] Failures:" / "[ERROR] Errors:": "[ERROR]   BarTest.testBaz:42 expected..."
+    static ref SUREFIRE_RECAP_HEADER_RE: Regex = Regex::new(r"^\[ERROR\] (?:Failures|Errors):\s*$").unwrap();
+    static ref SUREFIRE_RECAP_RE: Regex = Regex::new(r"^\[ERROR\]\s+(?:Run \d+: )?([\w$]+)\.([\w$]+)(?::\d+)?(?:\s|$)").unwrap();
+    static ref MAVEN_BANNER_RE: Regex = Regex::new(r"^(?:\[INFO\]\s+)?T E S T S$|^\[INFO\] BUILD (?:SUCCESS|FAILURE)").unwrap();
+
+    // Gradle test logging: "com.foo.BarTest > testBaz() FAILED"
+    static ref GRADLE_RESULT_RE: Regex = Regex::new(r"^(\S+) > (.+?) (PASSED|FAILED|SKIPPED)$").unwrap();
+    static ref GRADLE_BANNER_RE: Regex = Regex::new(r"^> Task :(?:\S+:)?test\b|^BUILD (?:SUCCESSFUL|FAILED)|^\d+ tests completed").unwrap();
+}
+
+/// Separator between `describe` titles and the test title in JavaScript test names
+pub const JS_NAME_SEPARATOR: &str = " > ";
+
+/// A test log format. Implement this and register it in
+/// `ParserRegistry::with_default_parsers` to support a new runner.
+pub trait LogParser: Send + Sync {
+    /// Stable name reported in `LogCounts::parser`
+    fn name(&self) -> &'static str;
+    /// How sure the parser is that `content` is in its format, from 0.0 to 1.0
+    fn detect(&self, content: &str) -> f32;
+    /// Extracts test statuses; `trace` records a `ParseEvent` for every match
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog;
+}
+
+pub struct ParserRegistry {
+    parsers: Vec<Box<dyn LogParser>>,
+}
+
+impl ParserRegistry {
+    pub fn new() -> Self {
+        ParserRegistry { parsers: Vec::new() }
+    }
+
+    /// Built-in parsers. On equal confidence the one registered first wins.
+    pub fn with_default_parsers() -> Self {
+        let mut registry = ParserRegistry::new();
+        registry.register(Box::new(NextestParser));
+        registry.register(Box::new(UiTestParser));
+        registry.register(Box::new(LibtestParser));
+        registry.register(Box::new(PytestParser));
+        registry.register(Box::new(GoTestParser));
+        registry.register(Box::new(JestParser));
+        registry.register(Box::new(MochaParser));
+        registry.register(Box::new(VitestParser));
+        registry.register(Box::new(JunitXmlParser));
+        registry.register(Box::new(LibtestJsonParser));
+        registry.register(Box::new(MavenSurefireParser));
+        registry.register(Box::new(GradleParser));
+        registry
+    }
+
+    pub fn register(&mut self, parser: Box<dyn LogParser>) {
+        self.parsers.push(parser);
+    }
+
+    pub fn parsers(&self) -> impl Iterator<Item = &dyn LogParser> {
+        self.parsers.iter().map(|parser| parser.as_ref())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn LogParser> {
+        self.parsers().find(|parser| parser.name() == name)
+    }
+
+    /// Confidence of every registered parser, in registration order
+    pub fn detect(&self, content: &str) -> Vec<(&'static str, f32)> {
+        self.parsers().map(|parser| (parser.name(), parser.detect(content))).collect()
+    }
+
+    /// Parses with the most confident parser and records the detection on the result
+    pub fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let scores = self.detect(content);
+        let mut best: Option<(&'static str, f32)> = None;
+        for &(name, score) in &scores {
+            if best.map(|(_, best_score)| score > best_score).unwrap_or(true) {
+                best = Some((name, score));
+            }
+        }
+        let Some((name, _)) = best else {
+            return ParsedLog::default();
+        };
+        self.parse_with_scores(name, content, trace, scores)
+    }
+
+    /// Parses with the named parser regardless of detection, which is still recorded for comparison
+    pub fn parse_as(&self, name: &str, content: &str, trace: bool) -> Result<ParsedLog, String> {
+        let parser = self.get(name).ok_or_else(|| {
+            let known: Vec<&str> = self.parsers().map(|parser| parser.name()).collect();
+            format!("Unknown log parser '{}', expected one of: {}", name, known.join(", "))
+        })?;
+        let mut parsed = self.parse_with_scores(parser.name(), content, trace, self.detect(content));
+        parsed.overridden = true;
+        Ok(parsed)
+    }
+
+    fn parse_with_scores(&self, name: &str, content: &str, trace: bool, scores: Vec<(&'static str, f32)>) -> ParsedLog {
+        let mut parsed = self.get(name).map(|parser| parser.parse(content, trace)).unwrap_or_default();
+        parsed.parser = name.to_string();
+        parsed.confidence = scores.iter().find(|(candidate, _)| *candidate == name).map(|(_, score)| *score).unwrap_or(0.0);
+        parsed.detection_scores = scores.into_iter().map(|(name, score)| (name.to_string(), score)).collect::<BTreeMap<_, _>>();
+        (parsed.outcome, parsed.outcome_reason) = detect_log_outcome(content, &parsed);
+        if parsed.suites.is_empty() {
+            parsed.suites = summary_line_totals(content);
+        }
+        parsed.doctests = parsed.all.iter()
+            .filter_map(|test| parse_doctest_name(test).map(|doctest| (test.clone(), doctest)))
+            .collect();
+        parsed
+    }
+}
+
+impl Default for ParserRegistry {
+    fn default() -> Self {
+        ParserRegistry::with_default_parsers()
+    }
+}
+
+// Plain `cargo test` output, including results split from their test line by debug output
+pub struct LibtestParser;
+
+impl LogParser for LibtestParser {
+    fn name(&self) -> &'static str {
+        "libtest"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        libtest_confidence(content)
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        parse_libtest_log(content, trace)
+    }
+}
+
+// `cargo nextest run` output ("PASS [ 0.01s] crate test::name"), mixed libtest lines included
+pub struct NextestParser;
+
+impl LogParser for NextestParser {
+    fn name(&self) -> &'static str {
+        "nextest"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        nextest_confidence(content)
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        parse_nextest_log(content, trace)
+    }
+}
+
+// UI-test style results ("tests/ui/foo.rs ... ok"), ANSI coloured and single-line logs
+pub struct UiTestParser;
+
+impl LogParser for UiTestParser {
+    fn name(&self) -> &'static str {
+        "ui_test"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        ui_test_confidence(content)
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        parse_rust_log_single_line(content, trace)
+    }
+}
+
+/// Whether an ANSI-free, trimmed line is a per-test result of one of the non-Rust runners
+pub(crate) fn is_result_line(line: &str) -> bool {
+    PYTEST_VERBOSE_RE.is_match(line) || PYTEST_SUMMARY_RE.is_match(line) || GO_RESULT_RE.is_match(line)
+        || JS_RESULT_RE.is_match(line) || JUNIT_LINE_RE.is_match(line)
+        || SUREFIRE_METHOD_RE.is_match(line) || SUREFIRE_CLASS_RE.is_match(line) || GRADLE_RESULT_RE.is_match(line)
+        || libtest_json_event(line).is_some_and(|event| {
+            event["type"] == "test" && matches!(event["event"].as_str(), Some("ok" | "failed" | "ignored" | "timeout"))
+        })
+}
+
+// Adds `name` under a normalized status: "passed", "failed" or "ignored"
+fn insert_status(parsed: &mut ParsedLog, name: String, status: &str) {
+    match status {
+        "passed" => { parsed.passed.insert(name.clone()); }
+        "failed" => { parsed.failed.insert(name.clone()); }
+        "ignored" => { parsed.ignored.insert(name.clone()); }
+        _ => return,
+    }
+    parsed.all.insert(name);
+}
+
+// pytest verbose (`-v`) results and `-rA` short summaries, named by node ID as in SWE-bench
+pub struct PytestParser;
+
+impl LogParser for PytestParser {
+    fn name(&self) -> &'static str {
+        "pytest"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        let mut result_lines = 0;
+        let mut has_banner = false;
+        for line in content.lines() {
+            let clean = strip_ansi_color_codes(line);
+            let line = clean.trim();
+            if PYTEST_VERBOSE_RE.is_match(line) || PYTEST_SUMMARY_RE.is_match(line) {
+                result_lines += 1;
+            } else if PYTEST_SESSION_RE.is_match(line) || (line.starts_with("platform ") && line.contains("-- Python")) {
+                has_banner = true;
+            }
+        }
+        if result_lines == 0 {
+            return if has_banner { 0.3 } else { 0.0 };
+        }
+        0.6 + 0.3 * (result_lines.min(10) as f32 / 10.0) + if has_banner { 0.1 } else { 0.0 }
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let mut recorder = TraceRecorder::new(trace, "pytest");
+        let mut parsed = ParsedLog::default();
+
+        for (i, raw) in content.lines().enumerate() {
+            let clean = strip_ansi_color_codes(raw);
+            let line = clean.trim();
+            let (pattern, status, name) = if let Some(captures) = PYTEST_VERBOSE_RE.captures(line) {
+                ("PYTEST_VERBOSE_RE", captures.get(2).unwrap().as_str(), captures.get(1).unwrap().as_str().to_string())
+            } else if let Some(captures) = PYTEST_SUMMARY_RE.captures(line) {
+                ("PYTEST_SUMMARY_RE", captures.get(1).unwrap().as_str(), pytest_node_id(captures.get(2).unwrap().as_str()).to_string())
+            } else {
+                continue;
+            };
+
+            // SWE-bench counts an expected failure as a pass
+            let status = match status {
+                "PASSED" | "XFAIL" | "XPASS" => "passed",
+                "FAILED" | "ERROR" => "failed",
+                _ => "ignored",
+            };
+            recorder.record(pattern, &name, status, Some(i + 1), raw);
+            insert_status(&mut parsed, name, status);
+        }
+
+        parsed.trace = recorder.into_events();
+        parsed
+    }
+}
+
+// Node ID at the start of a summary entry, without the " - message" tail.
+// Parametrized IDs may contain spaces, so a "[...]" suffix is kept whole.
+fn pytest_node_id(rest: &str) -> &str {
+    let rest = rest.trim();
+    let first_space = rest.find(' ').unwrap_or(rest.len());
+    if let Some(open) = rest[..first_space].find('[') {
+        let mut search_from = open;
+        while let Some(close) = rest[search_from..].find(']').map(|i| search_from + i) {
+            if rest[close + 1..].is_empty() || rest[close + 1..].starts_with(' ') {
+                return &rest[..=close];
+            }
+            search_from = close + 1;
+        }
+    }
+    &rest[..first_space]
+}
+
+// `go test -v` output. Subtests keep their full "TestParent/sub_case" path, which is how
+// main.json names them; tests without a result line take the status of their package summary.
+pub struct GoTestParser;
+
+impl LogParser for GoTestParser {
+    fn name(&self) -> &'static str {
+        "go_test"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        let mut test_lines = 0;
+        let mut has_package_summary = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if GO_RUN_RE.is_match(line) || GO_RESULT_RE.is_match(line) {
+                test_lines += 1;
+            } else if GO_PACKAGE_RE.is_match(line) {
+                has_package_summary = true;
+            }
+        }
+        if test_lines == 0 {
+            return 0.0;
+        }
+        0.6 + 0.3 * (test_lines.min(10) as f32 / 10.0) + if has_package_summary { 0.1 } else { 0.0 }
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let mut recorder = TraceRecorder::new(trace, "go_test");
+        let mut parsed = ParsedLog::default();
+        // Tests of the current package that started but have not reported yet
+        let mut running: Vec<String> = Vec::new();
+
+        for (i, raw) in content.lines().enumerate() {
+            let clean = strip_ansi_color_codes(raw);
+            let line = clean.trim();
+
+            if let Some(captures) = GO_RUN_RE.captures(line) {
+                let name = captures.get(1).unwrap().as_str().to_string();
+                if !running.contains(&name) {
+                    running.push(name);
+                }
+            } else if let Some(captures) = GO_RESULT_RE.captures(line) {
+                let name = captures.get(2).unwrap().as_str().to_string();
+                let status = match captures.get(1).unwrap().as_str() {
+                    "PASS" => "passed",
+                    "FAIL" => "failed",
+                    _ => "ignored",
+                };
+                recorder.record("GO_RESULT_RE", &name, status, Some(i + 1), raw);
+                running.retain(|started| *started != name);
+                if let Ok(seconds) = captures.get(3).unwrap().as_str().parse() {
+                    parsed.durations.insert(name.clone(), seconds);
+                }
+                insert_status(&mut parsed, name, status);
+            } else if let Some(captures) = GO_PACKAGE_RE.captures(line) {
+                // A panic or timeout aborts the package before the result lines are printed
+                let status = if captures.get(1).unwrap().as_str() == "ok" { "passed" } else { "failed" };
+                let note = format!("no result line, took the status of package {}", captures.get(2).unwrap().as_str());
+                for name in running.drain(..) {
+                    recorder.note("GO_PACKAGE_RE", &name, status, Some(i + 1), raw, &note);
+                    insert_status(&mut parsed, name, status);
+                }
+            }
+        }
+
+        parsed.trace = recorder.into_events();
+        parsed
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum JsRunner {
+    Jest,
+    Mocha,
+    Vitest,
+}
+
+// Jest output: "PASS src/x.test.js" file headers and ✓/✕/○ results nested under describe titles
+pub struct JestParser;
+
+impl LogParser for JestParser {
+    fn name(&self) -> &'static str {
+        "jest"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        js_confidence(content, JsRunner::Jest)
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        parse_js_tree(content, trace, JsRunner::Jest, self.name())
+    }
+}
+
+// Mocha spec reporter: ✓ passes, "1) title" failures and "- title" pending tests
+pub struct MochaParser;
+
+impl LogParser for MochaParser {
+    fn name(&self) -> &'static str {
+        "mocha"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        js_confidence(content, JsRunner::Mocha)
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        parse_js_tree(content, trace, JsRunner::Mocha, self.name())
+    }
+}
+
+// Vitest default and verbose reporters, as a tree or as flat "file > suite > test" lines
+pub struct VitestParser;
+
+impl LogParser for VitestParser {
+    fn name(&self) -> &'static str {
+        "vitest"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        js_confidence(content, JsRunner::Vitest)
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        parse_js_tree(content, trace, JsRunner::Vitest, self.name())
+    }
+}
+
+// The result markers are shared by all three runners, so the runner's own banner or summary decides
+fn js_confidence(content: &str, runner: JsRunner) -> f32 {
+    let mut result_lines = 0;
+    let mut has_banner = false;
+    for line in content.lines() {
+        let clean = strip_ansi_color_codes(line);
+        let line = clean.trim();
+        if JS_RESULT_RE.is_match(line) {
+            result_lines += 1;
+        }
+        has_banner |= match runner {
+            JsRunner::Jest => JEST_FILE_RE.is_match(line) || JEST_SUMMARY_RE.is_match(line),
+            JsRunner::Mocha => MOCHA_SUMMARY_RE.is_match(line),
+            JsRunner::Vitest => VITEST_BANNER_RE.is_match(line) || VITEST_SUMMARY_RE.is_match(line),
+        };
+    }
+    if result_lines == 0 {
+        return 0.0;
+    }
+    0.3 + 0.2 * (result_lines.min(10) as f32 / 10.0) + if has_banner { 0.4 } else { 0.0 }
+}
+
+// Rebuilds "describe > it" names from indentation: every non-result line is a suite title
+// that stays open for the deeper-indented lines below it.
+fn parse_js_tree(content: &str, trace: bool, runner: JsRunner, parser: &'static str) -> ParsedLog {
+    let mut recorder = TraceRecorder::new(trace, parser);
+    let mut parsed = ParsedLog::default();
+    let mut suites: Vec<(usize, String)> = Vec::new();
+    // Mocha repeats failures with numbers after its summary; those are not new tests
+    let mut after_mocha_summary = false;
+
+    for (i, raw) in content.lines().enumerate() {
+        let clean = strip_ansi_color_codes(raw);
+        let line = clean.trim_end();
+        let text = line.trim_start();
+        if text.is_empty() {
+            continue;
+        }
+        let indent = line.len() - text.len();
+
+        if JEST_FILE_RE.is_match(text) || VITEST_BANNER_RE.is_match(text) {
+            suites.clear();
+            after_mocha_summary = false;
+            continue;
+        }
+        if runner == JsRunner::Mocha && MOCHA_SUMMARY_RE.is_match(text) {
+            after_mocha_summary = true;
+            continue;
+        }
+        if after_mocha_summary {
+            continue;
+        }
+
+        suites.retain(|(depth, _)| *depth < indent);
+
+        let (pattern, status, title) = if let Some(captures) = JS_RESULT_RE.captures(text) {
+            let status = match captures.get(1).unwrap().as_str() {
+                "✓" | "✔" | "√" => "passed",
+                "✕" | "✖" | "×" => "failed",
+                _ => "ignored",
+            };
+            let title = captures.get(2).unwrap().as_str();
+            // Jest repeats the skip in words: "○ skipped renders copyright"
+            let title = match runner {
+                JsRunner::Jest if status == "ignored" => title.strip_prefix("skipped ").unwrap_or(title),
+                _ => title,
+            };
+            ("JS_RESULT_RE", status, title)
+        } else if let Some(captures) = MOCHA_FAILED_RE.captures(text).filter(|_| runner == JsRunner::Mocha) {
+            ("MOCHA_FAILED_RE", "failed", captures.get(1).unwrap().as_str())
+        } else if let Some(captures) = MOCHA_PENDING_RE.captures(text).filter(|_| runner == JsRunner::Mocha) {
+            ("MOCHA_PENDING_RE", "ignored", captures.get(1).unwrap().as_str())
+        } else {
+            if JS_FILE_TITLE_RE.is_match(text) {
+                suites.clear();
+            } else {
+                suites.push((indent, text.to_string()));
+            }
+            continue;
+        };
+
+        let duration_re: &Regex = if runner == JsRunner::Vitest { &VITEST_DURATION_RE } else { &JS_DURATION_RE };
+        let seconds = duration_re.captures(title).and_then(|captures| {
+            let value: f64 = captures[1].parse().ok()?;
+            Some(if &captures[2] == "ms" { value / 1000.0 } else { value })
+        });
+        let title = duration_re.replace(title, "");
+        if VITEST_COUNT_RE.is_match(&title) {
+            // A Vitest file or suite summary, not a test
+            let title = VITEST_COUNT_RE.replace(&title, "");
+            if JS_FILE_TITLE_RE.is_match(&title) {
+                suites.clear();
+            } else {
+                suites.push((indent, title.into_owned()));
+            }
+            continue;
+        }
+
+        let mut segments: Vec<&str> = title.split(JS_NAME_SEPARATOR).collect();
+        if segments.len() > 1 && JS_FILE_TITLE_RE.is_match(segments[0]) {
+            segments.remove(0);
+        }
+        let name = suites
+            .iter()
+            .map(|(_, suite)| suite.as_str())
+            .chain(segments)
+            .collect::<Vec<_>>()
+            .join(JS_NAME_SEPARATOR);
+        recorder.record(pattern, &name, status, Some(i + 1), raw);
+        if let Some(seconds) = seconds {
+            parsed.durations.insert(name.clone(), seconds);
+        }
+        insert_status(&mut parsed, name, status);
+    }
+
+    parsed.trace = recorder.into_events();
+    parsed
+}
+
+// JUnit XML reports (testsuite/testcase elements) from any runner. Tests get the name the
+// runner's text parser would give them: `Class.method` for Java, the node ID for pytest, and
+// the `name` attribute alone where it already holds the full name (Rust, Go, JavaScript).
+pub struct JunitXmlParser;
+
+impl LogParser for JunitXmlParser {
+    fn name(&self) -> &'static str {
+        "junit"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        if !content.contains("<testsuite") || !content.contains("<testcase") {
+            return 0.0;
+        }
+        // Structured results are exact, so a file that is a JUnit report beats the text heuristics.
+        // XML inside a text log (an echoed report, a fixture) leaves the log to its own runner.
+        let start = content.trim_start().trim_start_matches('\u{feff}');
+        if start.starts_with("<?xml") || start.starts_with("<testsuite") {
+            1.0
+        } else {
+            0.1
+        }
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let mut recorder = TraceRecorder::new(trace, "junit");
+        let mut parsed = ParsedLog::default();
+        let pytest = JUNIT_PYTEST_SUITE_RE.is_match(content);
+
+        for captures in JUNIT_TESTCASE_RE.captures_iter(content) {
+            let attributes = captures.get(1).unwrap().as_str();
+            let Some(name) = junit_attribute(&JUNIT_NAME_RE, attributes) else { continue };
+            let classname = junit_attribute(&JUNIT_CLASSNAME_RE, attributes);
+            let name = junit_test_name(classname.as_deref(), &name, pytest);
+            let body = captures.get(2).map(|m| m.as_str()).unwrap_or("");
+            let status = if JUNIT_FAILED_RE.is_match(body) {
+                "failed"
+            } else if JUNIT_SKIPPED_RE.is_match(body) {
+                "ignored"
+            } else {
+                "passed"
+            };
+
+            if recorder.enabled() {
+                let (line_number, line) = line_at(content, captures.get(0).unwrap().start());
+                match classname {
+                    Some(classname) => {
+                        let note = format!("classname {}", classname);
+                        recorder.note("JUNIT_TESTCASE_RE", &name, status, Some(line_number), line, &note);
+                    }
+                    None => recorder.record("JUNIT_TESTCASE_RE", &name, status, Some(line_number), line),
+                }
+            }
+            if let Some(seconds) = junit_attribute(&JUNIT_TIME_RE, attributes).and_then(|time| time.parse().ok()) {
+                parsed.durations.insert(name.clone(), seconds);
+            }
+            insert_status(&mut parsed, name, status);
+        }
+
+        parsed.trace = recorder.into_events();
+        parsed
+    }
+}
+
+// "com.acme.FooTest" + "testFoo()" -> "com.acme.FooTest.testFoo" as Surefire and Gradle name it;
+// pytest's "tests.test_x.TestA" + "test_b[1]" -> "tests/test_x.py::TestA::test_b[1]"
+fn junit_test_name(classname: Option<&str>, name: &str, pytest: bool) -> String {
+    let Some(classname) = classname.filter(|classname| !classname.is_empty() && !name.contains("::") && !name.starts_with(classname)) else {
+        return name.to_string();
+    };
+    if pytest {
+        // Modules come first and are lowercase by convention, classes are capitalized
+        let segments: Vec<&str> = classname.split('.').collect();
+        let module_end = segments.iter().position(|segment| segment.starts_with(char::is_uppercase)).unwrap_or(segments.len());
+        if module_end == 0 {
+            return name.to_string();
+        }
+        let path = format!("{}.py", segments[..module_end].join("/"));
+        return std::iter::once(path.as_str()).chain(segments[module_end..].iter().copied()).chain([name]).collect::<Vec<_>>().join("::");
+    }
+    if JAVA_CLASSNAME_RE.is_match(classname) {
+        return format!("{}.{}", classname, name.strip_suffix("()").unwrap_or(name));
+    }
+    name.to_string()
+}
+
+fn junit_attribute(re: &Regex, attributes: &str) -> Option<String> {
+    let captures = re.captures(attributes)?;
+    let value = captures.get(1).or_else(|| captures.get(2))?.as_str();
+    Some(
+        value
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&amp;", "&"),
+    )
+}
+
+// `cargo test -- -Z unstable-options --format json` and nextest's libtest-json event streams,
+// one `{"type": "suite"|"test", "event": ...}` object per line
+pub struct LibtestJsonParser;
+
+impl LogParser for LibtestJsonParser {
+    fn name(&self) -> &'static str {
+        "libtest_json"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        // Events are unambiguous, so any of them means the run was structured
+        if content.lines().any(|line| libtest_json_event(line).is_some()) {
+            0.95
+        } else {
+            0.0
+        }
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let mut recorder = TraceRecorder::new(trace, "libtest_json");
+        let mut parsed = ParsedLog::default();
+        // Tests of the current suite that started but have not finished, with their binary id
+        let mut running: Vec<(Option<String>, String)> = Vec::new();
+
+        for (i, raw) in content.lines().enumerate() {
+            let Some(event) = libtest_json_event(raw) else { continue };
+            let kind = event["event"].as_str().unwrap_or("");
+
+            if event["type"] == "suite" {
+                if kind == "started" {
+                    finish_crashed_tests(&mut parsed, &mut recorder, &mut running, i, raw);
+                } else if kind == "ok" || kind == "failed" {
+                    let count = |key: &str| event[key].as_u64().unwrap_or(0) as usize;
+                    parsed.suites.push(SuiteSummary {
+                        passed: count("passed"),
+                        failed: count("failed"),
+                        ignored: count("ignored"),
+                        exec_time: event["exec_time"].as_f64(),
+                        ..Default::default()
+                    });
+                    finish_crashed_tests(&mut parsed, &mut recorder, &mut running, i, raw);
+                }
+                continue;
+            }
+
+            let Some(id) = event["name"].as_str() else { continue };
+            let (binary, name) = split_json_test_id(id);
+            let status = match kind {
+                "started" => {
+                    let started = (binary.map(str::to_string), name);
+                    if !running.contains(&started) {
+                        running.push(started);
+                    }
+                    continue;
+                }
+                "ok" => "passed",
+                "failed" | "timeout" => "failed",
+                "ignored" => "ignored",
+                _ => continue,
+            };
+            running.retain(|(started_binary, started)| !(*started == name && started_binary.as_deref() == binary));
+
+            if let Some(exec_time) = event["exec_time"].as_f64() {
+                parsed.durations.insert(name.clone(), exec_time);
+            }
+            let stdout = event["stdout"].as_str().unwrap_or("");
+            if status == "failed" {
+                let mut detail = failure_detail_from_output(&name, stdout);
+                if kind == "timeout" {
+                    detail.kind = FailureKind::Timeout;
+                }
+                parsed.failures.insert(name.clone(), detail);
+            }
+            if !stdout.is_empty() {
+                parsed.stdout.insert(name.clone(), stdout.to_string());
+            }
+            if kind == "timeout" {
+                recorder.note("LIBTEST_JSON", &name, status, Some(i + 1), raw, "timed out");
+            } else {
+                recorder.record("LIBTEST_JSON", &name, status, Some(i + 1), raw);
+            }
+            if let Some(binary) = binary {
+                record_binary_status(&mut parsed.binaries, &name, binary, status);
+            }
+            insert_status(&mut parsed, name, status);
+        }
+        finish_crashed_tests(&mut parsed, &mut recorder, &mut running, content.lines().count(), "");
+
+        parsed.trace = recorder.into_events();
+        parsed
+    }
+}
+
+// A test that started but never finished before its suite ended took the test binary down with it
+fn finish_crashed_tests(parsed: &mut ParsedLog, recorder: &mut TraceRecorder, running: &mut Vec<(Option<String>, String)>, index: usize, line: &str) {
+    for (binary, name) in running.drain(..) {
+        recorder.note("LIBTEST_JSON", &name, "failed", Some(index + 1), line, "started but never finished before the suite ended");
+        if let Some(binary) = binary {
+            record_binary_status(&mut parsed.binaries, &name, &binary, "failed");
+        }
+        insert_status(parsed, name, "failed");
+    }
+}
+
+/// Splits a libtest-json test name into nextest's binary id and the test name:
+/// `my-crate::bin/tool$tests::it_works` becomes `(Some("my-crate::bin/tool"), "tests::it_works")`.
+/// cargo's own JSON output has no binary id.
+fn split_json_test_id(id: &str) -> (Option<&str>, String) {
+    match id.split_once('$') {
+        Some((binary, test)) => (Some(binary), test.to_string()),
+        None => (None, id.to_string()),
+    }
+}
+
+fn libtest_json_event(line: &str) -> Option<serde_json::Value> {
+    let line = line.trim();
+    if !line.starts_with('{') || !line.contains("\"type\"") {
+        return None;
+    }
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    matches!(event["type"].as_str(), Some("suite" | "test")).then_some(event)
+}
+
+// Maven Surefire console output. Passing methods are only listed with the plain report format,
+// so the "Tests run: ... - in Class" totals are kept per class in `suites`, where name
+// resolution looks up the methods the log does not list.
+pub struct MavenSurefireParser;
+
+impl LogParser for MavenSurefireParser {
+    fn name(&self) -> &'static str {
+        "maven"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        let mut result_lines = 0;
+        let mut has_banner = false;
+        for line in content.lines() {
+            let clean = strip_ansi_color_codes(line);
+            let line = clean.trim();
+            if SUREFIRE_CLASS_RE.is_match(line) || SUREFIRE_METHOD_RE.is_match(line) {
+                result_lines += 1;
+            } else if MAVEN_BANNER_RE.is_match(line) || SUREFIRE_RUNNING_RE.is_match(line) {
+                has_banner = true;
+            }
+        }
+        if result_lines == 0 {
+            return 0.0;
+        }
+        0.5 + 0.2 * (result_lines.min(10) as f32 / 10.0) + if has_banner { 0.3 } else { 0.0 }
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let mut recorder = TraceRecorder::new(trace, "maven");
+        let mut parsed = ParsedLog::default();
+        // Simple class name -> fully qualified name, from the "Running" lines
+        let mut classes: BTreeMap<String, String> = BTreeMap::new();
+        let mut in_recap = false;
+
+        for (i, raw) in content.lines().enumerate() {
+            let clean = strip_ansi_color_codes(raw);
+            let line = clean.trim();
+
+            if let Some(captures) = SUREFIRE_RUNNING_RE.captures(line) {
+                let class = captures.get(1).unwrap().as_str();
+                let simple = class.rsplit('.').next().unwrap_or(class);
+                classes.insert(simple.to_string(), class.to_string());
+                in_recap = false;
+            } else if let Some(captures) = SUREFIRE_CLASS_RE.captures(line) {
+                let count = |group: usize| captures.get(group).unwrap().as_str().parse::<usize>().unwrap_or(0);
+                let (run, failures, errors, skipped) = (count(1), count(2), count(3), count(4));
+                let class = captures.get(6).unwrap().as_str().to_string();
+                let status = if failures + errors > 0 {
+                    "failed"
+                } else if run > 0 && skipped == run {
+                    "ignored"
+                } else {
+                    "passed"
+                };
+                recorder.note("SUREFIRE_CLASS_RE", &class, status, Some(i + 1), raw, "class totals, not a test");
+                parsed.suites.push(SuiteSummary {
+                    name: Some(class),
+                    passed: run.saturating_sub(failures + errors + skipped),
+                    failed: failures + errors,
+                    ignored: skipped,
+                    exec_time: captures.get(5).and_then(|m| m.as_str().replace(',', ".").parse().ok()),
+                });
+            } else if let Some(captures) = SUREFIRE_METHOD_RE.captures(line) {
+                let name = surefire_method_name(captures.get(1).unwrap().as_str());
+                let status = match captures.get(3).map(|m| m.as_str()) {
+                    Some("FAILURE") | Some("ERROR") => "failed",
+                    Some(_) => "ignored",
+                    None => "passed",
+                };
+                if let Ok(seconds) = captures.get(2).unwrap().as_str().replace(',', ".").parse() {
+                    parsed.durations.insert(name.clone(), seconds);
+                }
+                recorder.record("SUREFIRE_METHOD_RE", &name, status, Some(i + 1), raw);
+                insert_status(&mut parsed, name, status);
+            } else if SUREFIRE_RECAP_HEADER_RE.is_match(line) {
+                in_recap = true;
+            } else if in_recap {
+                let Some(captures) = SUREFIRE_RECAP_RE.captures(line) else {
+                    in_recap = line.starts_with("[ERROR]");
+                    continue;
+                };
+                let simple = captures.get(1).unwrap().as_str();
+                let class = classes.get(simple).map(String::as_str).unwrap_or(simple);
+                let name = format!("{}.{}", class, captures.get(2).unwrap().as_str());
+                recorder.note("SUREFIRE_RECAP_RE", &name, "failed", Some(i + 1), raw, "listed in the failure recap");
+                insert_status(&mut parsed, name, "failed");
+            }
+        }
+
+        parsed.trace = recorder.into_events();
+        parsed
+    }
+}
+
+// "testBaz(com.foo.BarTest)" -> "com.foo.BarTest.testBaz"; Surefire 3 already prints "Class.method"
+fn surefire_method_name(printed: &str) -> String {
+    match printed.strip_suffix(')').and_then(|rest| rest.split_once('(')) {
+        Some((method, class)) if !method.is_empty() => format!("{}.{}", class, method),
+        _ => printed.to_string(),
+    }
+}
+
+// Gradle `testLogging` events: "com.foo.BarTest > testBaz() FAILED", named "com.foo.BarTest.testBaz"
+pub struct GradleParser;
+
+impl LogParser for GradleParser {
+    fn name(&self) -> &'static str {
+        "gradle"
+    }
+
+    fn detect(&self, content: &str) -> f32 {
+        let mut result_lines = 0;
+        let mut has_banner = false;
+        for line in content.lines() {
+            let clean = strip_ansi_color_codes(line);
+            let line = clean.trim();
+            if GRADLE_RESULT_RE.is_match(line) {
+                result_lines += 1;
+            } else if GRADLE_BANNER_RE.is_match(line) {
+                has_banner = true;
+            }
+        }
+        if result_lines == 0 {
+            return 0.0;
+        }
+        0.5 + 0.2 * (result_lines.min(10) as f32 / 10.0) + if has_banner { 0.3 } else { 0.0 }
+    }
+
+    fn parse(&self, content: &str, trace: bool) -> ParsedLog {
+        let mut recorder = TraceRecorder::new(trace, "gradle");
+        let mut parsed = ParsedLog::default();
+
+        for (i, raw) in content.lines().enumerate() {
+            let clean = strip_ansi_color_codes(raw);
+            let Some(captures) = GRADLE_RESULT_RE.captures(clean.trim()) else { continue };
+            // Nested classes print as "Outer > Inner > test()": the method is the last segment and
+            // the ones before it are inner classes, named `Outer$Inner` like the JVM does
+            let mut segments: Vec<&str> = captures.get(2).unwrap().as_str().split(" > ").collect();
+            let method = segments.pop().unwrap_or_default();
+            let method = method.strip_suffix("()").unwrap_or(method);
+            segments.insert(0, captures.get(1).unwrap().as_str());
+            let name = format!("{}.{}", segments.join("$"), method);
+            let status = match captures.get(3).unwrap().as_str() {
+                "PASSED" => "passed",
+                "FAILED" => "failed",
+                _ => "ignored",
+            };
+            recorder.record("GRADLE_RESULT_RE", &name, status, Some(i + 1), raw);
+            insert_status(&mut parsed, name, status);
+        }
+
+        parsed.trace = recorder.into_events();
+        parsed
+    }
+}
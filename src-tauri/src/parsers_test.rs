@@ -0,0 +1,633 @@
+use crate::analysis::{parse_doctest_name, FailureKind, LogOutcome};
+use crate::parsers::ParserRegistry;
+
+const LIBTEST_LOG: &str = "\
+running 3 tests
+test tests::a ... ok
+test tests::b ... FAILED
+test tests::c ... ignored
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored
+";
+
+const NEXTEST_LOG: &str = "\
+    Starting 3 tests across 1 binary
+        PASS [   0.010s] my-crate tests::a
+        FAIL [   0.020s] my-crate tests::b
+        SKIP [   0.000s] my-crate tests::c
+";
+
+#[test]
+fn highest_confidence_parser_wins() {
+    let registry = ParserRegistry::with_default_parsers();
+
+    let libtest = registry.parse(LIBTEST_LOG, false);
+    assert_eq!(libtest.parser, "libtest");
+    assert!(libtest.failed.contains("tests::b"));
+    assert_eq!(libtest.detection_scores.len(), registry.parsers().count());
+    assert_eq!(libtest.confidence, libtest.detection_scores["libtest"]);
+
+    let nextest = registry.parse(NEXTEST_LOG, false);
+    assert_eq!(nextest.parser, "nextest");
+    assert!(nextest.passed.contains("tests::a"));
+    assert!(nextest.ignored.contains("tests::c"));
+
+    let ui = registry.parse("tests/ui/a.rs ... ok\ntests/ui/b.rs ... FAILED\n", false);
+    assert_eq!(ui.parser, "ui_test");
+    assert!(ui.failed.contains("tests/ui/b.rs"));
+}
+
+#[test]
+fn nextest_mention_alone_does_not_route_to_nextest() {
+    let log = format!("+ cargo install cargo-nextest || cargo nextest run --help\n{}", LIBTEST_LOG);
+    let registry = ParserRegistry::with_default_parsers();
+
+    let scores = registry.detect(&log);
+    let nextest = scores.iter().find(|(name, _)| *name == "nextest").unwrap().1;
+    let libtest = scores.iter().find(|(name, _)| *name == "libtest").unwrap().1;
+    assert!(nextest > 0.0 && nextest < libtest, "scores: {:?}", scores);
+    assert_eq!(registry.parse(&log, false).parser, "libtest");
+}
+
+#[test]
+fn aborts_are_told_apart_from_tests_named_after_them() {
+    let registry = ParserRegistry::with_default_parsers();
+
+    let completed = registry.parse("\
+running 2 tests
+test alloc::handles_out_of_memory ... ok
+test signals::reports_segmentation_fault ... ok
+
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+", false);
+    assert_eq!(completed.outcome, LogOutcome::Completed);
+
+    let aborted = registry.parse("\
+running 2 tests
+test alloc::handles_out_of_memory ... ok
+memory allocation of 68719476736 bytes failed
+error: test failed, to rerun pass `--lib`
+
+Caused by:
+  process didn't exit successfully: `/repo/target/debug/deps/demo-1a2b3c` (signal: 6, SIGABRT: process abort signal)
+", false);
+    assert_eq!(aborted.outcome, LogOutcome::Aborted);
+    assert_eq!(aborted.outcome_reason.as_deref(), Some("line 3: memory allocation of 68719476736 bytes failed"));
+}
+
+#[test]
+fn a_few_nextest_lines_in_a_libtest_log_route_to_nextest() {
+    let log = format!(
+        "{}running 3 tests\ntest tests::d ... ok\ntest tests::e ... ok\ntest tests::f ... ok\n        PASS [   0.010s] my-crate tests::g\n",
+        LIBTEST_LOG
+    );
+    let registry = ParserRegistry::with_default_parsers();
+
+    // The nextest parser reads both kinds of result line, the libtest parser only its own
+    let parsed = registry.parse(&log, false);
+    assert_eq!(parsed.parser, "nextest");
+    assert!(parsed.passed.contains("tests::g"));
+    assert!(parsed.passed.contains("tests::e"));
+    assert!(parsed.failed.contains("tests::b"));
+}
+
+#[test]
+fn ansi_coloured_results_go_to_the_single_line_parser() {
+    let log = "test tests::a ... \x1b[32mok\x1b[0m\ntest tests::b ... \x1b[31mFAILED\x1b[0m\n";
+    let registry = ParserRegistry::with_default_parsers();
+
+    let parsed = registry.parse(log, false);
+    assert_eq!(parsed.parser, "ui_test");
+    assert!(parsed.passed.contains("tests::a"));
+    assert!(parsed.failed.contains("tests::b"));
+    assert!(registry.get("libtest").unwrap().detect(log) < parsed.confidence);
+}
+
+const PYTEST_LOG: &str = "\
+============================= test session starts ==============================
+platform linux -- Python 3.11.4, pytest-7.4.0, pluggy-1.2.0
+collected 6 items
+
+tests/test_api.py::TestClient::test_get PASSED                           [ 16%]
+tests/test_api.py::test_parse[1-2] PASSED                                [ 33%]
+tests/test_api.py::test_parse[a b] FAILED                                [ 50%]
+tests/test_api.py::test_skip SKIPPED (not supported)                     [ 66%]
+
+=========================== short test summary info ============================
+PASSED tests/test_api.py::TestClient::test_get
+PASSED tests/test_api.py::test_parse[1-2]
+XFAIL tests/test_api.py::test_known_bug - reason: flaky upstream
+SKIPPED [1] tests/test_api.py:42: not supported
+FAILED tests/test_api.py::test_parse[a b] - AssertionError: assert 1 == 2
+ERROR tests/test_api.py::test_fixture - RuntimeError: boom
+";
+
+#[test]
+fn pytest_log_is_detected_and_named_by_node_id() {
+    let registry = ParserRegistry::with_default_parsers();
+    let parsed = registry.parse(PYTEST_LOG, false);
+
+    assert_eq!(parsed.parser, "pytest");
+    assert!(parsed.passed.contains("tests/test_api.py::TestClient::test_get"));
+    assert!(parsed.passed.contains("tests/test_api.py::test_parse[1-2]"));
+    assert!(parsed.passed.contains("tests/test_api.py::test_known_bug"));
+    assert!(parsed.failed.contains("tests/test_api.py::test_parse[a b]"));
+    assert!(parsed.failed.contains("tests/test_api.py::test_fixture"));
+    assert!(parsed.ignored.contains("tests/test_api.py::test_skip"));
+    // "SKIPPED [1] file:line" summary entries carry no node ID
+    assert!(!parsed.all.iter().any(|name| name.contains(":42")));
+    assert_eq!(parsed.all.len(), 6);
+
+    // Rust logs are not mistaken for pytest output
+    assert_eq!(registry.get("pytest").unwrap().detect(LIBTEST_LOG), 0.0);
+}
+
+const GO_LOG: &str = "\
+=== RUN   TestParse
+=== RUN   TestParse/empty_input
+=== RUN   TestParse/unicode
+--- FAIL: TestParse (0.00s)
+    --- PASS: TestParse/empty_input (0.00s)
+    --- FAIL: TestParse/unicode (0.00s)
+=== RUN   TestSkipped
+--- SKIP: TestSkipped (0.00s)
+FAIL
+FAIL\tgithub.com/acme/lib/parser\t0.012s
+=== RUN   TestServe
+panic: runtime error: invalid memory address or nil pointer dereference
+FAIL\tgithub.com/acme/lib/server\t0.020s
+=== RUN   TestCached
+--- PASS: TestCached (0.00s)
+PASS
+ok  \tgithub.com/acme/lib/cache\t(cached)
+";
+
+#[test]
+fn go_test_log_keeps_subtest_paths_and_uses_package_summaries() {
+    let registry = ParserRegistry::with_default_parsers();
+    let parsed = registry.parse(GO_LOG, true);
+
+    assert_eq!(parsed.parser, "go_test");
+    assert!(parsed.failed.contains("TestParse"));
+    assert!(parsed.passed.contains("TestParse/empty_input"));
+    assert!(parsed.failed.contains("TestParse/unicode"));
+    assert!(parsed.ignored.contains("TestSkipped"));
+    assert!(parsed.passed.contains("TestCached"));
+    // Panicked before reporting, so only the package FAIL line says what happened
+    assert!(parsed.failed.contains("TestServe"));
+    let serve = parsed.trace.iter().find(|event| event.test == "TestServe").unwrap();
+    assert_eq!(serve.pattern, "GO_PACKAGE_RE");
+    assert_eq!(serve.line_number, Some(13));
+}
+
+const JEST_LOG: &str = " PASS  src/components/Header.test.js
+  Header
+    ✓ renders header (5 ms)
+    with user
+      ✓ shows the avatar (2 ms)
+    ✓ times out after 100ms (101 ms)
+ FAIL  src/components/Footer.test.js
+  Footer
+    ✕ renders links (3 ms)
+    ○ skipped renders copyright
+  ✓ top level test (1 ms)
+
+  ● Footer › renders links
+
+    expect(received).toBe(expected)
+
+Tests:       1 failed, 1 skipped, 4 passed, 6 total
+";
+
+const MOCHA_LOG: &str = "  Array
+    #indexOf()
+      ✔ should return -1 when the value is not present
+      1) should return the index
+      - should handle NaN
+  String
+    ✔ trims (120ms)
+
+  2 passing (130ms)
+  1 pending
+  1 failing
+
+  1) Array
+       #indexOf()
+         should return the index:
+     AssertionError: expected -1 to equal 0
+";
+
+const VITEST_LOG: &str = " RUN  v1.2.0 /repo
+
+ ✓ src/math.test.ts (3 tests) 4ms
+   ✓ math (3)
+     ✓ adds 1ms
+     × divides by zero 2ms
+     ↓ rounds
+ ✓ src/flat.test.ts > parser > reads numbers 1ms
+
+ Test Files  2 passed (2)
+";
+
+#[test]
+fn javascript_logs_rebuild_describe_hierarchy() {
+    let registry = ParserRegistry::with_default_parsers();
+
+    let jest = registry.parse(JEST_LOG, false);
+    assert_eq!(jest.parser, "jest");
+    assert!(jest.passed.contains("Header > renders header"));
+    assert!(jest.passed.contains("Header > with user > shows the avatar"));
+    assert!(jest.failed.contains("Footer > renders links"));
+    assert!(jest.ignored.contains("Footer > renders copyright"));
+    // Only the parenthesized duration is stripped, a time in the title stays
+    assert!(jest.passed.contains("Header > times out after 100ms"));
+    assert!(jest.passed.contains("top level test"));
+    assert_eq!(jest.all.len(), 6);
+
+    let mocha = registry.parse(MOCHA_LOG, false);
+    assert_eq!(mocha.parser, "mocha");
+    assert!(mocha.passed.contains("Array > #indexOf() > should return -1 when the value is not present"));
+    assert!(mocha.failed.contains("Array > #indexOf() > should return the index"));
+    assert!(mocha.ignored.contains("Array > #indexOf() > should handle NaN"));
+    assert!(mocha.passed.contains("String > trims"));
+    // The numbered failure recap after the summary is not counted again
+    assert_eq!(mocha.all.len(), 4);
+
+    let vitest = registry.parse(VITEST_LOG, false);
+    assert_eq!(vitest.parser, "vitest");
+    assert!(vitest.passed.contains("math > adds"));
+    assert!(vitest.failed.contains("math > divides by zero"));
+    assert!(vitest.ignored.contains("math > rounds"));
+    assert!(vitest.passed.contains("parser > reads numbers"));
+    assert_eq!(vitest.all.len(), 4);
+}
+
+const LIBTEST_JSON_LOG: &str = r#"   Compiling demo v0.1.0 (/repo)
+    Finished `test` profile [unoptimized + debuginfo] target(s) in 1.20s
+     Running unittests src/lib.rs (target/debug/deps/demo-1a2b3c)
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "tests::adds" }
+{ "type": "test", "event": "started", "name": "tests::divides" }
+{ "type": "test", "event": "started", "name": "tests::rounds" }
+{ "type": "test", "name": "tests::adds", "event": "ok", "exec_time": 0.004 }
+{ "type": "test", "name": "tests::divides", "event": "failed", "exec_time": 0.012, "stdout": "thread 'tests::divides' panicked at src/lib.rs:20:9:\nattempt to divide by zero\n" }
+{ "type": "test", "name": "tests::rounds", "event": "ignored" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1, "ignored": 1, "measured": 0, "filtered_out": 0, "exec_time": 0.013 }
+{"type":"suite","event":"started","test_count":2,"nextest":{"crate":"demo","test_binary":"integration","kind":"test"}}
+{"type":"test","event":"started","name":"demo::integration$it_works"}
+{"type":"test","event":"ok","name":"demo::integration$it_works","exec_time":0.5}
+{"type":"test","event":"started","name":"demo::integration$overflows"}
+"#;
+
+#[test]
+fn libtest_json_events_are_parsed_exactly() {
+    let registry = ParserRegistry::with_default_parsers();
+    let parsed = registry.parse(LIBTEST_JSON_LOG, true);
+
+    assert_eq!(parsed.parser, "libtest_json");
+    assert!(parsed.passed.contains("tests::adds"));
+    assert!(parsed.failed.contains("tests::divides"));
+    assert!(parsed.ignored.contains("tests::rounds"));
+    // The nextest binary id is not part of the test name, but is kept per test
+    assert!(parsed.passed.contains("it_works"));
+    assert_eq!(parsed.binaries["it_works"]["demo::integration"], "passed");
+    assert_eq!(parsed.binaries["overflows"]["demo::integration"], "failed");
+    assert!(!parsed.binaries.contains_key("tests::adds"));
+    // Still running when the log ended, so the binary crashed under it
+    assert!(parsed.failed.contains("overflows"));
+
+    assert_eq!(parsed.durations["tests::divides"], 0.012);
+    assert_eq!(parsed.durations["it_works"], 0.5);
+    assert!(parsed.stdout["tests::divides"].contains("attempt to divide by zero"));
+    let detail = &parsed.failures["tests::divides"];
+    assert_eq!(detail.location.as_deref(), Some("src/lib.rs:20:9"));
+    assert_eq!(detail.message.as_deref(), Some("attempt to divide by zero"));
+    assert_eq!(parsed.suites.len(), 1);
+    assert_eq!((parsed.suites[0].passed, parsed.suites[0].failed, parsed.suites[0].ignored), (1, 1, 1));
+}
+
+#[test]
+fn failures_are_classified_from_their_own_lines() {
+    let log = "\
+running 3 tests
+test tests::foo_bar has been running for over 60 seconds
+test tests::foo ... FAILED
+test tests::foo_bar ... FAILED
+test tests::reads_config ... FAILED
+
+failures:
+
+---- tests::reads_config stdout ----
+thread 'tests::reads_config' panicked at src/config.rs:12:30:
+called `Result::unwrap()` on an `Err` value: Os { code: 2, kind: NotFound, message: \"No such file or directory\" }
+
+failures:
+    tests::foo
+    tests::foo_bar
+    tests::reads_config
+
+test result: FAILED. 0 passed; 3 failed; 0 ignored; 0 measured; 0 filtered out; finished in 60.01s
+";
+    let parsed = ParserRegistry::with_default_parsers().parse(log, false);
+
+    assert_eq!(parsed.failures["tests::foo_bar"].kind, FailureKind::Timeout);
+    // The timeout line of tests::foo_bar says nothing about tests::foo
+    assert_ne!(parsed.failures["tests::foo"].kind, FailureKind::Timeout);
+    // An I/O error the test unwraps is the test's own panic, not the environment's
+    assert_eq!(parsed.failures["tests::reads_config"].kind, FailureKind::Panic);
+}
+
+const MAVEN_LOG: &str = "\
+[INFO] -------------------------------------------------------
+[INFO]  T E S T S
+[INFO] -------------------------------------------------------
+[INFO] Running com.acme.ParserTest
+[ERROR] Tests run: 3, Failures: 1, Errors: 0, Skipped: 0, Time elapsed: 0.05 s <<< FAILURE! - in com.acme.ParserTest
+[ERROR] testUnicode(com.acme.ParserTest)  Time elapsed: 0.01 s  <<< FAILURE!
+java.lang.AssertionError: expected:<1> but was:<2>
+[INFO] Running com.acme.CacheTest
+[INFO] Tests run: 2, Failures: 0, Errors: 0, Skipped: 0, Time elapsed: 0.02 s - in com.acme.CacheTest
+[INFO] Running com.acme.ServerTest
+[ERROR] Tests run: 1, Failures: 0, Errors: 1, Skipped: 0, Time elapsed: 0.1 s <<< FAILURE! - in com.acme.ServerTest
+[INFO] Running com.acme.LexerTest
+[ERROR] Tests run: 2, Failures: 1, Errors: 0, Skipped: 0, Time elapsed: 0.03 s <<< FAILURE! -- in com.acme.LexerTest
+[ERROR] com.acme.LexerTest.testEscapes -- Time elapsed: 0.02 s <<< FAILURE!
+[INFO]
+[INFO] Results:
+[INFO]
+[ERROR] Failures: 
+[ERROR]   ParserTest.testUnicode:42 expected:<1> but was:<2>
+[ERROR] Errors: 
+[ERROR]   ServerTest.testStart:17 » NullPointer
+[INFO]
+[ERROR] Tests run: 8, Failures: 2, Errors: 1, Skipped: 0
+[INFO] BUILD FAILURE
+";
+
+const GRADLE_LOG: &str = "\
+> Task :test
+
+com.acme.ParserTest > testEmpty() PASSED
+
+com.acme.ParserTest > testUnicode() FAILED
+    java.lang.AssertionError at ParserTest.java:42
+
+com.acme.ParserTest > Nested > testDeep() SKIPPED
+
+com.acme.ParserTest > Nested > Deeper > testDeepest() PASSED
+
+4 tests completed, 1 failed, 1 skipped
+BUILD FAILED in 3s
+";
+
+#[test]
+fn java_logs_are_named_class_dot_method() {
+    let registry = ParserRegistry::with_default_parsers();
+
+    let maven = registry.parse(MAVEN_LOG, false);
+    assert_eq!(maven.parser, "maven");
+    assert!(maven.failed.contains("com.acme.ParserTest.testUnicode"));
+    // Only named in the recap, by simple class name
+    assert!(maven.failed.contains("com.acme.ServerTest.testStart"));
+    // Surefire 3 spelling
+    assert!(maven.failed.contains("com.acme.LexerTest.testEscapes"));
+    assert_eq!(maven.durations["com.acme.ParserTest.testUnicode"], 0.01);
+    // Class totals are kept as suites, not as tests
+    assert_eq!(maven.all.len(), 3);
+    assert_eq!(maven.suites.len(), 4);
+    assert_eq!(maven.suites[0].name.as_deref(), Some("com.acme.ParserTest"));
+    assert_eq!(maven.suites[0].passed, 2);
+    assert_eq!(maven.suites[3].name.as_deref(), Some("com.acme.LexerTest"));
+
+    let gradle = registry.parse(GRADLE_LOG, false);
+    assert_eq!(gradle.parser, "gradle");
+    assert!(gradle.passed.contains("com.acme.ParserTest.testEmpty"));
+    assert!(gradle.failed.contains("com.acme.ParserTest.testUnicode"));
+}
+
+#[test]
+fn gradle_nested_classes_keep_every_enclosing_class() {
+    let gradle = ParserRegistry::with_default_parsers().parse(GRADLE_LOG, false);
+
+    assert!(gradle.ignored.contains("com.acme.ParserTest$Nested.testDeep"));
+    assert!(gradle.passed.contains("com.acme.ParserTest$Nested$Deeper.testDeepest"));
+    assert!(!gradle.all.iter().any(|name| name == "com.acme.ParserTest.testDeep"));
+}
+
+const SUREFIRE_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuite name="com.acme.ParserTest" tests="3" failures="1">
+  <testcase name="testEmpty" classname="com.acme.ParserTest" time="0.002"/>
+  <testcase name="testUnicode()" classname="com.acme.ParserTest" time="0.010">
+    <failure message="expected:&lt;1&gt; but was:&lt;2&gt;"/>
+  </testcase>
+  <testcase name="testEmpty" classname="com.acme.LexerTest" time="0.001"/>
+</testsuite>
+"#;
+
+const PYTEST_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites><testsuite name="pytest" errors="0" failures="1" skipped="0" tests="3">
+  <testcase classname="tests.test_parser" name="test_empty" time="0.001"/>
+  <testcase classname="tests.test_parser.TestUnicode" name="test_decode[utf-8]" time="0.002">
+    <failure message="AssertionError">assert 1 == 2</failure>
+  </testcase>
+  <testcase classname="tests.test_lexer" name="test_empty" time="0.001"/>
+</testsuite></testsuites>
+"#;
+
+#[test]
+fn junit_reports_are_named_like_the_text_logs() {
+    let registry = ParserRegistry::with_default_parsers();
+
+    let java = registry.parse(SUREFIRE_XML, false);
+    assert_eq!(java.parser, "junit");
+    assert!(java.passed.contains("com.acme.ParserTest.testEmpty"));
+    assert!(java.failed.contains("com.acme.ParserTest.testUnicode"));
+    // Same method name in another class stays a separate test
+    assert!(java.passed.contains("com.acme.LexerTest.testEmpty"));
+    assert_eq!(java.all.len(), 3);
+
+    let pytest = registry.parse(PYTEST_XML, false);
+    assert!(pytest.passed.contains("tests/test_parser.py::test_empty"));
+    assert!(pytest.failed.contains("tests/test_parser.py::TestUnicode::test_decode[utf-8]"));
+    assert!(pytest.passed.contains("tests/test_lexer.py::test_empty"));
+    assert_eq!(pytest.all.len(), 3);
+}
+
+#[test]
+fn junit_xml_echoed_into_a_text_log_does_not_take_it_over() {
+    let log = format!("+ cat target/surefire-reports/TEST-com.acme.ParserTest.xml\n{}{}", SUREFIRE_XML, NEXTEST_LOG);
+    let registry = ParserRegistry::with_default_parsers();
+
+    let scores = registry.detect(&log);
+    let junit = scores.iter().find(|(name, _)| *name == "junit").unwrap().1;
+    assert!(junit > 0.0 && junit < 0.5, "scores: {:?}", scores);
+    assert_eq!(registry.parse(&log, false).parser, "nextest");
+    // A report with leading whitespace is still a report
+    assert_eq!(registry.parse(&format!("\n  {}", SUREFIRE_XML), false).parser, "junit");
+}
+
+#[test]
+fn nextest_summary_covers_the_whole_run() {
+    let log = format!(
+        "{}------------\n     Summary [   0.030s] 3 tests run: 1 passed, 1 failed, 1 skipped\n\ntest result: ok. 4 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n",
+        NEXTEST_LOG
+    );
+    let registry = ParserRegistry::with_default_parsers();
+    let parsed = registry.parse(&log, false);
+
+    assert_eq!(parsed.parser, "nextest");
+    assert_eq!(parsed.suites.len(), 1);
+    assert_eq!((parsed.suites[0].passed, parsed.suites[0].failed, parsed.suites[0].ignored), (1, 1, 1));
+    assert_eq!(parsed.suites[0].exec_time, Some(0.03));
+}
+
+#[test]
+fn results_keep_the_binary_that_reported_them() {
+    let libtest = "\
+     Running unittests src/lib.rs (target/debug/deps/core-1a2b3c4d5e6f7a8b)
+
+running 1 test
+test tests::it_works ... ok
+
+     Running unittests src/lib.rs (target/debug/deps/parser-0f1e2d3c4b5a6978)
+
+running 1 test
+test tests::it_works ... FAILED
+";
+    let nextest = "\
+        PASS [   0.010s] core tests::it_works
+        FAIL [   0.020s] parser tests::it_works
+";
+    let registry = ParserRegistry::with_default_parsers();
+    for log in [libtest, nextest] {
+        let parsed = registry.parse(log, false);
+        let per_binary = &parsed.binaries["tests::it_works"];
+        assert_eq!(per_binary.get("core").map(String::as_str), Some("passed"), "{}", parsed.parser);
+        assert_eq!(per_binary.get("parser").map(String::as_str), Some("failed"), "{}", parsed.parser);
+        assert!(parsed.failed.contains("tests::it_works"));
+    }
+}
+
+#[test]
+fn doctests_are_identified_by_file_and_item() {
+    let log = "\
+   Doc-tests parser
+
+running 3 tests
+test src/lib.rs - (line 1) ... ok
+test src/lib.rs - Parser::parse (line 42) ... ok
+test src/lexer.rs - Lexer<'a>::new (line 7) - compile fail ... ok
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.30s
+";
+    let parsed = ParserRegistry::with_default_parsers().parse(log, false);
+
+    assert_eq!(parsed.doctests.len(), 3);
+    let parse = &parsed.doctests["src/lib.rs - Parser::parse (line 42)"];
+    assert_eq!((parse.file.as_str(), parse.item.as_str(), parse.line), ("src/lib.rs", "Parser::parse", 42));
+    assert_eq!(parsed.doctests["src/lib.rs - (line 1)"].item, "");
+    let new = &parsed.doctests["src/lexer.rs - Lexer<'a>::new (line 7) - compile fail"];
+    assert_eq!((new.item.as_str(), new.variant.as_deref()), ("Lexer<'a>::new", Some("compile fail")));
+    let moved = parse_doctest_name("src/lib.rs - Parser::parse (line 45)").unwrap();
+    assert!(moved.same_example(parse));
+    assert_eq!(parsed.binaries["src/lib.rs - (line 1)"].keys().collect::<Vec<_>>(), vec!["parser (doctests)"]);
+}
+
+#[test]
+fn nextest_retries_end_with_the_last_try() {
+    let log = "\
+    Starting 3 tests across 1 binary (retries: 2)
+        PASS [   0.010s] my-crate tests::a
+   TRY 1 FAIL [   0.020s] my-crate tests::b
+  RETRY 2/3 [         ] my-crate tests::b
+   TRY 2 PASS [   0.015s] my-crate tests::b
+   TRY 1 FAIL [   0.020s] my-crate tests::c
+  RETRY 2/3 [         ] my-crate tests::c
+   TRY 2 FAIL [   0.020s] my-crate tests::c
+------------
+     Summary [   0.065s] 3 tests run: 2 passed (1 flaky), 1 failed, 0 skipped
+   FLAKY 2/3 [   0.015s] my-crate tests::b
+   TRY 2 FAIL [   0.020s] my-crate tests::c
+";
+    let parsed = ParserRegistry::with_default_parsers().parse(log, false);
+
+    assert_eq!(parsed.parser, "nextest");
+    assert!(parsed.passed.contains("tests::b") && !parsed.failed.contains("tests::b"));
+    assert!(parsed.failed.contains("tests::c"));
+    assert_eq!(parsed.flaky.iter().collect::<Vec<_>>(), vec!["tests::b"]);
+    assert_eq!(parsed.retries["tests::b"], vec!["failed", "passed"]);
+    assert_eq!(parsed.retries["tests::c"], vec!["failed", "failed"]);
+    assert_eq!(parsed.binaries["tests::b"]["my-crate"], "passed");
+}
+
+#[test]
+fn a_retry_in_one_binary_leaves_the_same_name_in_another_alone() {
+    let log = "\
+        FAIL [   0.030s] other-crate tests::it_works
+   TRY 1 FAIL [   0.020s] my-crate tests::it_works
+  RETRY 2/2 [         ] my-crate tests::it_works
+   TRY 2 PASS [   0.015s] my-crate tests::it_works
+        PASS [   0.010s] other-crate tests::stable
+   TRY 1 FAIL [   0.020s] my-crate tests::stable
+  RETRY 2/2 [         ] my-crate tests::stable
+   TRY 2 FAIL [   0.020s] my-crate tests::stable
+";
+    let parsed = ParserRegistry::with_default_parsers().parse(log, false);
+
+    // The flaky pass in my-crate does not hide other-crate's failure
+    assert!(parsed.failed.contains("tests::it_works") && !parsed.passed.contains("tests::it_works"));
+    assert_eq!(parsed.binaries["tests::it_works"]["my-crate"], "passed");
+    assert_eq!(parsed.binaries["tests::it_works"]["other-crate"], "failed");
+    // Nor does other-crate's plain pass disappear behind my-crate's failed retries
+    assert_eq!(parsed.binaries["tests::stable"]["other-crate"], "passed");
+    assert_eq!(parsed.binaries["tests::stable"]["my-crate"], "failed");
+    assert!(parsed.failed.contains("tests::stable"));
+    assert_eq!(parsed.durations["tests::it_works"], 0.03);
+}
+
+#[test]
+fn per_test_durations_are_kept_where_the_log_has_them() {
+    let registry = ParserRegistry::with_default_parsers();
+
+    let nextest = registry.parse("        PASS [   2.877s] my-crate tests::a\n        FAIL [   0.020s] my-crate tests::b\n", false);
+    assert_eq!(nextest.durations["tests::a"], 2.877);
+    assert_eq!(nextest.durations["tests::b"], 0.02);
+
+    let libtest = registry.parse("running 2 tests\ntest tests::a ... ok <1.250s>\ntest tests::b ... ok\n", false);
+    assert_eq!(libtest.parser, "libtest");
+    assert_eq!(libtest.durations.get("tests::a"), Some(&1.25));
+    assert!(libtest.passed.contains("tests::a"));
+    assert!(!libtest.durations.contains_key("tests::b"));
+
+    let go = registry.parse("=== RUN   TestA\n--- PASS: TestA (1.50s)\nPASS\nok  \tgithub.com/acme/lib\t1.512s\n", false);
+    assert_eq!(go.durations["TestA"], 1.5);
+
+    let jest = registry.parse(JEST_LOG, false);
+    assert_eq!(jest.durations["Header > renders header"], 0.005);
+    assert!(!jest.durations.contains_key("Footer > renders copyright"));
+    let vitest = registry.parse(VITEST_LOG, false);
+    assert_eq!(vitest.durations["math > divides by zero"], 0.002);
+}
+
+#[test]
+fn hanging_nextest_tests_are_timed() {
+    let registry = ParserRegistry::with_default_parsers();
+    let log = "\
+        PASS [   0.010s] my-crate tests::a
+        SLOW [> 60.000s] my-crate tests::hangs
+        SLOW [>120.000s] my-crate tests::hangs
+     TIMEOUT [ 180.003s] my-crate tests::hangs
+        SLOW [> 60.000s] my-crate tests::still_running
+";
+
+    let parsed = registry.parse(log, false);
+
+    assert_eq!(parsed.parser, "nextest");
+    assert!(parsed.failed.contains("tests::hangs"));
+    assert_eq!(parsed.failures["tests::hangs"].kind, FailureKind::Timeout);
+    assert_eq!(parsed.durations["tests::hangs"], 180.003);
+    assert_eq!(parsed.binaries["tests::hangs"]["my-crate"], "failed");
+    // A slow notice is not a result, but says how long the test ran at least
+    assert!(!parsed.all.contains("tests::still_running"));
+    assert_eq!(parsed.durations["tests::still_running"], 60.0);
+}
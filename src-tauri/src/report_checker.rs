@@ -3,6 +3,8 @@ use std::fs;
 use tempfile::TempDir;
 use crate::auth::{GoogleTokens, tokens_path, save_google_tokens, refresh_access_token};
 use crate::drive::{extract_drive_folder_id, get_folder_contents, get_folder_metadata};
+use crate::analysis::{analyze_logs, detect_file_roles, AnalysisReport, Decision, DeliverableFiles};
+use crate::rules::compute_score;
 // load_setting is not used in this module
 
 #[derive(Serialize, Deserialize)]
@@ -25,6 +27,20 @@ pub struct DownloadResult {
     pub downloaded_files: Vec<FileInfo>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct ProcessingResult {
+    pub status: String, // "completed", or "incomplete" when required files are missing
+    pub message: String,
+    pub files_processed: usize,
+    pub issues_found: usize,
+    pub score: u32,
+    pub file_paths: Vec<String>,
+    pub roles: DeliverableFiles,
+    pub decision: Option<Decision>,
+    pub summary: String,
+    pub report: Option<AnalysisReport>,
+}
+
 pub async fn validate_deliverable(folder_link: String) -> Result<ValidationResult, String> {
     // Rule 1: The link should be accessible and Rule 2: Should be to a folder not a file
     let folder_id = extract_drive_folder_id(&folder_link)
@@ -155,16 +171,17 @@ pub async fn validate_deliverable(folder_link: String) -> Result<ValidationResul
         "_post_agent_patch.log",
     ];
     
+    // Each role needs its text log or a JUnit XML report ("_after.xml", "_after_junit.xml")
     for suffix in &required_suffixes {
         let suffix_lower = suffix.to_lowercase();
         let has_file = log_files.iter().any(|file| {
             let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-            file_name.ends_with(&suffix_lower) &&
+            (file_name.ends_with(&suffix_lower) || is_junit_for_suffix(&file_name, &suffix_lower)) &&
             file["mimeType"].as_str() != Some("application/vnd.google-apps.folder")
         });
         
         if !has_file {
-            return Err(format!("Missing required log file ending with: {} (case insensitive search)", suffix));
+            return Err(format!("Missing required log file ending with: {} or a JUnit XML report for it (case insensitive search)", suffix));
         }
     }
     
@@ -184,12 +201,18 @@ pub async fn validate_deliverable(folder_link: String) -> Result<ValidationResul
         });
     }
     
-    // 2. Add the 4 log files
+    // 2. Add the 4 log files and any JUnit XML reports next to them
     for suffix in &required_suffixes {
-        if let Some(log_file) = log_files.iter().find(|file| {
+        let suffix_lower = suffix.to_lowercase();
+        let log_file = log_files.iter().find(|file| {
+            let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
+            file_name.ends_with(&suffix_lower)
+        });
+        let junit_file = log_files.iter().find(|file| {
             let file_name = file["name"].as_str().unwrap_or("").to_lowercase();
-            file_name.ends_with(&suffix.to_lowercase())
-        }) {
+            is_junit_for_suffix(&file_name, &suffix_lower)
+        });
+        for log_file in log_file.into_iter().chain(junit_file) {
             files_to_download.push(FileInfo {
                 id: log_file["id"].as_str().unwrap_or("").to_string(),
                 name: log_file["name"].as_str().unwrap_or("").to_string(),
@@ -294,6 +317,13 @@ pub async fn validate_deliverable(folder_link: String) -> Result<ValidationResul
     })
 }
 
+// Whether a lowercase file name is the JUnit XML counterpart of a "_role.log" suffix
+fn is_junit_for_suffix(file_name: &str, log_suffix: &str) -> bool {
+    let Some(stem) = file_name.strip_suffix(".xml") else { return false };
+    let stem = stem.trim_end_matches("junit").trim_end_matches(['.', '_', '-']);
+    stem.ends_with(log_suffix.trim_end_matches(".log"))
+}
+
 pub async fn download_deliverable(files_to_download: Vec<FileInfo>, folder_id: String) -> Result<DownloadResult, String> {
     use reqwest::header::AUTHORIZATION;
     
@@ -456,23 +486,70 @@ pub async fn download_deliverable(files_to_download: Vec<FileInfo>, folder_id: S
     })
 }
 
-pub async fn process_deliverable(downloaded_files: Vec<FileInfo>) -> Result<serde_json::Value, String> {
-    // Dummy processing with 5 second delay
-    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-    
-    // For now, just pass the file paths to the result
-    // Later, this will do actual processing
+pub async fn process_deliverable(downloaded_files: Vec<FileInfo>) -> Result<ProcessingResult, String> {
     let file_paths: Vec<String> = downloaded_files.iter().map(|f| f.path.clone()).collect();
-    
-    // Simulate processing results
-    Ok(serde_json::json!({
-        "status": "completed",
-        "message": "Report processing completed successfully",
-        "files_processed": downloaded_files.len(),
-        "issues_found": 3,
-        "score": 85,
-        "file_paths": file_paths
-    }))
+    let roles = detect_file_roles(&file_paths);
+    println!("Detected deliverable roles: {:?}", roles);
+
+    // Without the required inputs there is nothing to analyze; report what is missing instead of failing
+    let missing = roles.missing_required();
+    if !missing.is_empty() {
+        return Ok(ProcessingResult {
+            status: "incomplete".to_string(),
+            message: format!("Missing required files: {}", missing.join(", ")),
+            files_processed: downloaded_files.len(),
+            issues_found: 0,
+            score: 0,
+            file_paths,
+            roles,
+            decision: None,
+            summary: String::new(),
+            report: None,
+        });
+    }
+
+    let report = analyze_logs(file_paths.clone()).await?;
+    let issues_found = report.verdict.reasons.len();
+    let score = compute_score(&report.rule_checks);
+    let summary = summarize_report(&report);
+    println!("Processing summary: {}", summary);
+
+    Ok(ProcessingResult {
+        status: "completed".to_string(),
+        message: "Report processing completed successfully".to_string(),
+        files_processed: downloaded_files.len(),
+        issues_found,
+        score,
+        file_paths,
+        roles,
+        decision: Some(report.verdict.decision),
+        summary,
+        report: Some(report),
+    })
+}
+
+// One-line human readable digest of the verdict, e.g. "reject: 2 rule(s) failed (c1_..., c3_...); 3 F2P, 10 P2P",
+// followed by the number of parser health warnings and test name collisions if there are any
+fn summarize_report(report: &AnalysisReport) -> String {
+    let decision = match report.verdict.decision {
+        Decision::Accept => "accept",
+        Decision::Reject => "reject",
+        Decision::NeedsManualReview => "needs manual review",
+    };
+    let failed = if report.verdict.reasons.is_empty() {
+        "no rule failed".to_string()
+    } else {
+        let ids: Vec<&str> = report.verdict.reasons.iter().map(|reason| reason.rule_id.as_str()).collect();
+        format!("{} rule(s) failed ({})", ids.len(), ids.join(", "))
+    };
+    let mut summary = format!("{}: {}; {} F2P, {} P2P", decision, failed, report.counts.f2p, report.counts.p2p);
+    if !report.parser_health.is_empty() {
+        summary.push_str(&format!("; {} parser warning(s)", report.parser_health.len()));
+    }
+    if !report.binary_collisions.is_empty() {
+        summary.push_str(&format!("; {} ambiguous test name(s)", report.binary_collisions.len()));
+    }
+    summary
 }
 
 pub fn get_file_content(file_type: String, file_paths: Vec<String>) -> Result<String, String> {
@@ -0,0 +1,1009 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::cmp::min;
+use crate::settings::{load_setting, save_setting};
+use crate::analysis::{
+    detect_same_file_duplicates, is_status_line, js_test_title, line_names_test, search_in_log_content, Decision, Evidence, FailureKind, ParsedLog, RuleCheck, Verdict, VerdictReason,
+    C1_FAILED_IN_BASE_PRESENT_IN_P2P, C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P, C3_F2P_SUCCESS_IN_BEFORE,
+    C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE, C5_DUPLICATES_IN_SAME_LOG_FOR_F2P_OR_P2P,
+    C6_TEST_MARKED_FAILED_IN_REPORT_BUT_PASSING_IN_AGENT, C7_F2P_TESTS_IN_GOLDEN_SOURCE_DIFF,
+    C8_F2P_FAILING_IN_BEFORE_FOR_WRONG_REASON, C9_BUILD_FAILED_IN_LOG, C10_FLAKY_IN_ANY_LOG_PRESENT_IN_F2P_OR_P2P,
+    C11_F2P_OR_P2P_SLOWER_THAN_THRESHOLD, LogOutcome,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// Settings key holding the per-rule configuration as a JSON object keyed by rule id
+pub const RULE_CONFIG_SETTING: &str = "rule_config";
+
+/// Per-rule overrides. Unset fields fall back to the rule's own defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleConfig {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub severity: Option<Severity>,
+    #[serde(default)]
+    pub example_limit: Option<usize>,
+}
+
+pub(crate) fn default_enabled() -> bool {
+    true
+}
+
+impl Default for RuleConfig {
+    fn default() -> Self {
+        RuleConfig { enabled: true, severity: None, example_limit: None }
+    }
+}
+
+pub fn load_rule_configs() -> Result<BTreeMap<String, RuleConfig>, String> {
+    let raw = load_setting(RULE_CONFIG_SETTING.to_string())?;
+    if raw.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    serde_json::from_str(&raw)
+        .map_err(|e| format!("Failed to parse {} setting: {}", RULE_CONFIG_SETTING, e))
+}
+
+pub fn save_rule_configs(configs: BTreeMap<String, RuleConfig>) -> Result<(), String> {
+    let raw = serde_json::to_string(&configs)
+        .map_err(|e| format!("Failed to serialize rule config: {}", e))?;
+    save_setting(RULE_CONFIG_SETTING.to_string(), raw)
+}
+
+/// Stored configuration merged with the defaults of every registered rule,
+/// so the settings UI always sees the complete list.
+pub fn effective_rule_configs() -> Result<BTreeMap<String, RuleConfig>, String> {
+    let stored = load_rule_configs()?;
+    let registry = RuleRegistry::with_default_rules();
+    Ok(registry.rules().map(|rule| {
+        let config = stored.get(rule.id()).cloned().unwrap_or_default();
+        (rule.id().to_string(), RuleConfig {
+            enabled: config.enabled,
+            severity: Some(config.severity.unwrap_or(rule.severity())),
+            example_limit: config.example_limit.or(rule.default_example_limit()),
+        })
+    }).collect())
+}
+
+/// Everything a rule may look at. Status maps hold "passed", "failed",
+/// "ignored" or "missing" for every F2P/P2P test.
+pub struct AnalysisContext<'a> {
+    pub fail_to_pass: &'a [String],
+    pub pass_to_pass: &'a [String],
+    pub universe: Vec<String>,
+    pub base_parsed: &'a ParsedLog,
+    pub before_parsed: &'a ParsedLog,
+    pub after_parsed: &'a ParsedLog,
+    pub agent_parsed: Option<&'a ParsedLog>,
+    pub base_status: HashMap<String, String>,
+    pub before_status: HashMap<String, String>,
+    pub after_status: HashMap<String, String>,
+    pub agent_status: HashMap<String, String>,
+    pub report_status: HashMap<String, String>,
+    /// Per log role, the name the log reports an F2P/P2P test under when it differs from main.json
+    pub log_names: HashMap<&'static str, HashMap<String, String>>,
+    pub base_path: &'a str,
+    pub before_path: &'a str,
+    pub after_path: &'a str,
+    pub agent_path: Option<&'a str>,
+    pub report_data: Option<&'a serde_json::Value>,
+    pub file_paths: &'a [String],
+    // Seconds above which an F2P/P2P test counts as too slow
+    pub slow_test_threshold: f64,
+}
+
+fn status_of<'m>(statuses: &'m HashMap<String, String>, test_name: &str) -> &'m str {
+    statuses.get(test_name).map(String::as_str).unwrap_or("missing")
+}
+
+/// Name the log playing `role` keys a test's failures, retries and durations by
+pub(crate) fn log_name_of<'m>(log_names: &'m HashMap<&'static str, HashMap<String, String>>, role: &str, test_name: &'m str) -> &'m str {
+    log_names.get(role).and_then(|names| names.get(test_name)).map(String::as_str).unwrap_or(test_name)
+}
+
+impl<'a> AnalysisContext<'a> {
+    pub fn base(&self, test_name: &str) -> &str {
+        status_of(&self.base_status, test_name)
+    }
+
+    pub fn before(&self, test_name: &str) -> &str {
+        status_of(&self.before_status, test_name)
+    }
+
+    pub fn after(&self, test_name: &str) -> &str {
+        status_of(&self.after_status, test_name)
+    }
+
+    pub fn agent(&self, test_name: &str) -> &str {
+        status_of(&self.agent_status, test_name)
+    }
+
+    pub fn report(&self, test_name: &str) -> &str {
+        status_of(&self.report_status, test_name)
+    }
+
+    pub fn log_name<'t>(&'t self, role: &str, test_name: &'t str) -> &'t str {
+        log_name_of(&self.log_names, role, test_name)
+    }
+
+    /// Path of the log playing `role` ("base", "before", "after" or "agent")
+    pub fn log_path(&self, role: &str) -> Option<&str> {
+        match role {
+            "base" => Some(self.base_path),
+            "before" => Some(self.before_path),
+            "after" => Some(self.after_path),
+            "agent" => self.agent_path,
+            _ => None,
+        }
+    }
+
+    /// Parsed log playing `role`
+    pub fn parsed(&self, role: &str) -> Option<&ParsedLog> {
+        match role {
+            "base" => Some(self.base_parsed),
+            "before" => Some(self.before_parsed),
+            "after" => Some(self.after_parsed),
+            "agent" => self.agent_parsed,
+            _ => None,
+        }
+    }
+
+    /// Logs whose run never got to the tests because the build failed
+    pub fn failed_builds(&self) -> Vec<&'static str> {
+        ALL_LOGS.iter()
+            .copied()
+            .filter(|role| self.parsed(role).is_some_and(|parsed| parsed.outcome == LogOutcome::BuildFailed))
+            .collect()
+    }
+}
+
+/// Every log role, in the order evidence is reported
+pub const ALL_LOGS: &[&str] = &["base", "before", "after", "agent"];
+
+/// Upper bound on the tests per rule that get evidence when the rule has no example limit
+const EVIDENCE_TEST_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Default)]
+pub struct RuleOutcome {
+    pub examples: Vec<String>,
+    /// Names of the tests behind the examples, used as verdict evidence
+    pub tests: Vec<String>,
+    // Findings grouped by log label, for rules that report per log (C5)
+    pub examples_per_log: Option<BTreeMap<String, Vec<String>>>,
+}
+
+impl RuleOutcome {
+    /// Outcome whose examples are plain test names
+    pub fn from_examples(examples: Vec<String>) -> Self {
+        RuleOutcome { tests: examples.clone(), examples, examples_per_log: None }
+    }
+
+    pub fn new(examples: Vec<String>, tests: Vec<String>) -> Self {
+        RuleOutcome { examples, tests, examples_per_log: None }
+    }
+
+    pub fn has_problem(&self) -> bool {
+        !self.examples.is_empty()
+            || self.examples_per_log.as_ref().map(|m| !m.is_empty()).unwrap_or(false)
+    }
+}
+
+/// A single acceptance check. Implement this and register it in
+/// `RuleRegistry::with_default_rules` to add a new check to the analysis.
+pub trait Rule: Send + Sync {
+    /// Stable key under which the result appears in `AnalysisReport::rule_checks`
+    fn id(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+    /// Maximum number of examples kept in the report, `None` for all of them
+    fn default_example_limit(&self) -> Option<usize> {
+        None
+    }
+    /// Logs whose status lines prove a violation of this rule
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        ALL_LOGS
+    }
+    /// Logs that must have run their tests for the outcome to mean anything, because the rule
+    /// reports tests missing from them. A build failure in one of them suppresses the rule.
+    fn needs_completed_logs(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome;
+}
+
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl RuleRegistry {
+    pub fn new() -> Self {
+        RuleRegistry { rules: Vec::new() }
+    }
+
+    pub fn with_default_rules() -> Self {
+        let mut registry = RuleRegistry::new();
+        // First, so a build failure leads the verdict reasons of its severity
+        registry.register(Box::new(BuildFailedInLog));
+        registry.register(Box::new(P2pFailedInBase));
+        registry.register(Box::new(FailedInAfter));
+        registry.register(Box::new(F2pPassingInBefore));
+        registry.register(Box::new(P2pMissingInBaseAndBefore));
+        registry.register(Box::new(DuplicatesInSameLog));
+        registry.register(Box::new(ReportAgentMismatch));
+        registry.register(Box::new(F2pInGoldenSourceDiff));
+        registry.register(Box::new(F2pBeforeFailureNotGenuine));
+        registry.register(Box::new(FlakyInAnyLog));
+        registry.register(Box::new(SlowerThanThreshold));
+        registry
+    }
+
+    pub fn register(&mut self, rule: Box<dyn Rule>) {
+        self.rules.push(rule);
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Runs every enabled rule against the context, keyed by rule id.
+    /// Disabled rules are still listed, without being evaluated.
+    pub fn evaluate(&self, ctx: &AnalysisContext, configs: &BTreeMap<String, RuleConfig>) -> BTreeMap<String, RuleCheck> {
+        let mut checks = BTreeMap::new();
+        // Each log is read at most once, and only if some rule needs evidence from it
+        let mut log_contents: HashMap<&str, Option<String>> = HashMap::new();
+        // Rules counting missing tests would report every test of a log that failed to build
+        let failed_builds = ctx.failed_builds();
+        let build_rule_enabled = configs.get(C9_BUILD_FAILED_IN_LOG).map(|config| config.enabled).unwrap_or(true);
+        for rule in self.rules() {
+            let config = configs.get(rule.id()).cloned().unwrap_or_default();
+            let severity = config.severity.unwrap_or(rule.severity());
+            let example_limit = config.example_limit.or(rule.default_example_limit());
+            let suppressed_by = (build_rule_enabled
+                && rule.needs_completed_logs().iter().any(|log| failed_builds.contains(log)))
+                .then(|| C9_BUILD_FAILED_IN_LOG.to_string());
+
+            let outcome = if !config.enabled {
+                println!("Rule {} is disabled, skipping", rule.id());
+                RuleOutcome::default()
+            } else if suppressed_by.is_some() {
+                println!("Rule {} is suppressed by a build failure in {:?}", rule.id(), failed_builds);
+                RuleOutcome::default()
+            } else {
+                rule.evaluate(ctx)
+            };
+            let has_problem = outcome.has_problem();
+            let evidence = if has_problem {
+                let evidence_tests = &outcome.tests[..outcome.tests.len().min(example_limit.unwrap_or(EVIDENCE_TEST_LIMIT))];
+                collect_evidence(ctx, rule.evidence_logs(), evidence_tests, &mut log_contents)
+            } else {
+                Vec::new()
+            };
+            let limit = |examples: Vec<String>| match example_limit {
+                Some(limit) => examples.into_iter().take(limit).collect(),
+                None => examples,
+            };
+
+            checks.insert(rule.id().to_string(), RuleCheck {
+                has_problem,
+                description: rule.description().to_string(),
+                severity,
+                enabled: config.enabled,
+                example_limit,
+                examples: limit(outcome.examples),
+                tests: outcome.tests,
+                duplicate_examples_per_log: outcome.examples_per_log
+                    .map(|per_log| per_log.into_iter().map(|(log, examples)| (log, limit(examples))).collect()),
+                evidence,
+                suppressed_by,
+            });
+        }
+        checks
+    }
+}
+
+// Status lines mentioning each test in the given logs, with the surrounding context
+fn collect_evidence(
+    ctx: &AnalysisContext,
+    logs: &[&'static str],
+    tests: &[String],
+    log_contents: &mut HashMap<&'static str, Option<String>>,
+) -> Vec<Evidence> {
+    let mut evidence = Vec::new();
+    for &log in logs {
+        let content = log_contents.entry(log).or_insert_with(|| {
+            ctx.log_path(log).and_then(|path| fs::read_to_string(path).ok())
+        });
+        let Some(content) = content else { continue };
+        for test in tests {
+            for hit in search_in_log_content(content, test) {
+                // search_in_log_content only returns a bare JavaScript title under its own describe blocks
+                let names_test = line_names_test(&hit.line_content, test)
+                    || js_test_title(test).is_some_and(|title| line_names_test(&hit.line_content, title));
+                if !is_status_line(&hit.line_content) || !names_test {
+                    continue;
+                }
+                evidence.push(Evidence {
+                    test: test.clone(),
+                    log: log.to_string(),
+                    line_number: hit.line_number,
+                    line_content: hit.line_content,
+                    context_before: hit.context_before,
+                    context_after: hit.context_after,
+                });
+            }
+        }
+    }
+    evidence
+}
+
+/// Combines rule outcomes into a single decision. Any failing error-severity
+/// rule rejects, a failing warning asks for manual review, info never changes
+/// the decision. Reasons are ordered by severity, then by registration order.
+pub fn compute_verdict(registry: &RuleRegistry, checks: &BTreeMap<String, RuleCheck>) -> Verdict {
+    let mut reasons: Vec<VerdictReason> = registry.rules()
+        .filter_map(|rule| checks.get(rule.id()).map(|check| (rule.id(), check)))
+        .filter(|(_, check)| check.enabled && check.has_problem)
+        .map(|(id, check)| VerdictReason {
+            rule_id: id.to_string(),
+            severity: check.severity,
+            message: check.description.clone(),
+            tests: check.tests.clone(),
+        })
+        .collect();
+    // Stable sort keeps registration order within a severity
+    reasons.sort_by_key(|reason| severity_rank(reason.severity));
+
+    let decision = match reasons.first().map(|reason| reason.severity) {
+        Some(Severity::Error) => Decision::Reject,
+        Some(Severity::Warning) => Decision::NeedsManualReview,
+        Some(Severity::Info) | None => Decision::Accept,
+    };
+
+    Verdict { decision, reasons }
+}
+
+/// 0-100 quality score: the severity-weighted share of enabled rules that
+/// passed. Errors weigh 3, warnings 2, info 1; with no enabled rules it's 100.
+/// Rules suppressed by a build failure never ran, so they count neither way.
+pub fn compute_score(checks: &BTreeMap<String, RuleCheck>) -> u32 {
+    let (passed, total) = checks.values()
+        .filter(|check| check.enabled && check.suppressed_by.is_none())
+        .fold((0u32, 0u32), |(passed, total), check| {
+            let weight = severity_weight(check.severity);
+            let passed = if check.has_problem { passed } else { passed + weight };
+            (passed, total + weight)
+        });
+    if total == 0 {
+        return 100;
+    }
+    passed * 100 / total
+}
+
+fn severity_weight(severity: Severity) -> u32 {
+    match severity {
+        Severity::Error => 3,
+        Severity::Warning => 2,
+        Severity::Info => 1,
+    }
+}
+
+fn severity_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Error => 0,
+        Severity::Warning => 1,
+        Severity::Info => 2,
+    }
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        RuleRegistry::with_default_rules()
+    }
+}
+
+// C9: a log whose build failed has no test results. In before that is expected when the test
+// patch uses new API, so it is only reported there when P2P tests needed before to pass.
+pub struct BuildFailedInLog;
+
+impl Rule for BuildFailedInLog {
+    fn id(&self) -> &'static str {
+        C9_BUILD_FAILED_IN_LOG
+    }
+
+    fn description(&self) -> &'static str {
+        "The build failed in at least one log, so none of its tests ran"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    // The reason line is part of the example
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        // With every P2P test passing in base, before only has to show the F2P tests failing
+        let p2p_needs_before = ctx.pass_to_pass.iter().any(|t| ctx.base(t) != "passed");
+        let hits = ctx.failed_builds().into_iter().filter(|role| *role != "before" || p2p_needs_before).map(|role| {
+            let reason = ctx.parsed(role).and_then(|parsed| parsed.outcome_reason.as_deref()).unwrap_or_default();
+            format!("build failed in {}.log ({})", role, reason)
+        }).collect();
+        RuleOutcome::new(hits, Vec::new())
+    }
+}
+
+// C1: P2P tests that failed in base
+pub struct P2pFailedInBase;
+
+impl Rule for P2pFailedInBase {
+    fn id(&self) -> &'static str {
+        C1_FAILED_IN_BASE_PRESENT_IN_P2P
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one failed test in base log is present in P2P"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["base"]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        RuleOutcome::from_examples(ctx.pass_to_pass.iter()
+            .filter(|t| ctx.base(t) == "failed")
+            .cloned()
+            .collect())
+    }
+}
+
+// C2: failed in after (not: "not passed")
+pub struct FailedInAfter;
+
+impl Rule for FailedInAfter {
+    fn id(&self) -> &'static str {
+        C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one failed test in after log is present in F2P / P2P"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["after"]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        RuleOutcome::from_examples(ctx.universe.iter()
+            .filter(|t| ctx.after(t) == "failed")
+            .cloned()
+            .collect())
+    }
+}
+
+// C3: F2P tests that already pass before the golden patch
+pub struct F2pPassingInBefore;
+
+impl Rule for F2pPassingInBefore {
+    fn id(&self) -> &'static str {
+        C3_F2P_SUCCESS_IN_BEFORE
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one F2P test is present and successful in before log"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["before"]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        RuleOutcome::from_examples(ctx.fail_to_pass.iter()
+            .filter(|t| ctx.before(t) == "passed")
+            .cloned()
+            .collect())
+    }
+}
+
+// C4: P2P tests that are missing in base and not passing in before
+// Logic:
+// - If P2P passed in base → Skip (don't check)
+// - If P2P is missing in base → Check before:
+//   - If passing in before → No violation
+//   - If missing or failed in before → Violation
+pub struct P2pMissingInBaseAndBefore;
+
+impl Rule for P2pMissingInBaseAndBefore {
+    fn id(&self) -> &'static str {
+        C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one P2P, that is missing in base, and is found but failing in before or is missing from base and before"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["base", "before"]
+    }
+
+    // A base that failed to build leaves every P2P test missing; a failed before only means
+    // it cannot make up for tests missing in base, which is still reported
+    fn needs_completed_logs(&self) -> &'static [&'static str] {
+        &["base"]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        let mut hits = vec![];
+        let mut tests = vec![];
+        for t in ctx.pass_to_pass {
+            let b = ctx.base(t);
+            let be = ctx.before(t);
+
+            // If P2P is missing in base and NOT passing in before (missing or failed), it's a violation
+            if b == "missing" && be != "passed" {
+                hits.push(format!("{t} (missing in base, {be} in before)"));
+                tests.push(t.clone());
+            }
+        }
+        RuleOutcome::new(hits, tests)
+    }
+}
+
+// C5: true duplicates per log using enhanced detection
+pub struct DuplicatesInSameLog;
+
+impl Rule for DuplicatesInSameLog {
+    fn id(&self) -> &'static str {
+        C5_DUPLICATES_IN_SAME_LOG_FOR_F2P_OR_P2P
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one F2P / P2P test name is duplicated (present 2 times in the same logs)"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["base", "before", "after"]
+    }
+
+    fn default_example_limit(&self) -> Option<usize> {
+        Some(50)
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        let mut dup_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        let mut tests: Vec<String> = vec![];
+        for (label, path) in [("base", ctx.base_path), ("before", ctx.before_path), ("after", ctx.after_path)] {
+            let text = fs::read_to_string(path).unwrap_or_default();
+            let dups = detect_same_file_duplicates(&text);
+            if !dups.is_empty() {
+                for (name, _) in &dups {
+                    if !tests.contains(name) {
+                        tests.push(name.clone());
+                    }
+                }
+                dup_map.insert(label.to_string(), dups.into_iter().map(|(_, example)| example).collect());
+            }
+        }
+        RuleOutcome { examples: vec![], tests, examples_per_log: Some(dup_map) }
+    }
+}
+
+// C6: Test marked as failing in report.json but passing in post_agent_log
+// This checks for inconsistencies between report.json and agent log results
+pub struct ReportAgentMismatch;
+
+impl Rule for ReportAgentMismatch {
+    fn id(&self) -> &'static str {
+        C6_TEST_MARKED_FAILED_IN_REPORT_BUT_PASSING_IN_AGENT
+    }
+
+    fn description(&self) -> &'static str {
+        "Test status mismatch between report.json and agent log"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["agent"]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        let (Some(_agent_parsed), Some(report_data)) = (ctx.agent_parsed, ctx.report_data) else {
+            println!("C6 check skipped: missing agent log or report.json");
+            return RuleOutcome::default();
+        };
+        println!("Performing C6 check: comparing report.json with agent log results");
+
+        let report_failed_tests = report_failed_tests(report_data);
+        println!("Found {} failed tests in report.json", report_failed_tests.len());
+
+        // Check F2P and P2P tests for inconsistencies in both directions
+        let mut hits = vec![];
+        let mut tests = vec![];
+        for test_name in &ctx.universe {
+            let report_status = if report_failed_tests.contains(test_name) {
+                "failed"
+            } else if ctx.report(test_name) == "passed" {
+                "passed"
+            } else {
+                "missing" // Skip tests that are missing in report.json
+            };
+
+            let agent_status = ctx.agent(test_name);
+
+            // Check for status mismatches (excluding missing cases)
+            if report_status != "missing" && agent_status != "missing" && report_status != agent_status {
+                match (report_status, agent_status) {
+                    ("failed", "passed") => {
+                        hits.push(format!("{} (marked as failed in report.json but passing in agent log)", test_name));
+                        tests.push(test_name.clone());
+                    },
+                    ("passed", "failed") => {
+                        hits.push(format!("{} (marked as passed in report.json but failing in agent log)", test_name));
+                        tests.push(test_name.clone());
+                    },
+                    _ => {} // Other combinations like "passed" vs "ignored" could be added if needed
+                }
+            }
+        }
+
+        println!("C6 check found {} inconsistencies", hits.len());
+        RuleOutcome::new(hits, tests)
+    }
+}
+
+// Parse report.json to extract failed tests
+// Common formats: results array, test_results array, direct test mapping, or SWE-bench format
+fn report_failed_tests(report_data: &serde_json::Value) -> HashSet<String> {
+    let mut report_failed_tests = HashSet::new();
+    let is_failed = |status: &str| status.to_lowercase() == "failed" || status.to_lowercase() == "fail";
+
+    // Try different possible structures for report.json
+    if let Some(results_array) = report_data.get("results").and_then(|r| r.as_array()) {
+        for result in results_array {
+            if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
+                if is_failed(status) {
+                    report_failed_tests.insert(test_name.to_string());
+                }
+            }
+        }
+    } else if let Some(test_results) = report_data.get("test_results").and_then(|r| r.as_array()) {
+        for result in test_results {
+            if let (Some(test_name), Some(status)) = (result.get("test_name").and_then(|t| t.as_str()), result.get("status").and_then(|s| s.as_str())) {
+                if is_failed(status) {
+                    report_failed_tests.insert(test_name.to_string());
+                }
+            }
+        }
+    } else if let Some(tests_obj) = report_data.get("tests").and_then(|t| t.as_object()) {
+        // Format: {"tests": {"test_name": {"status": "failed"}}}
+        for (test_name, test_data) in tests_obj {
+            if let Some(status) = test_data.get("status").and_then(|s| s.as_str()) {
+                if is_failed(status) {
+                    report_failed_tests.insert(test_name.clone());
+                }
+            }
+        }
+    } else if let Some(obj) = report_data.as_object() {
+        // Check for SWE-bench format first
+        let mut found_swe_format = false;
+        for (key, value) in obj {
+            if let Some(tests_status) = value.get("tests_status").and_then(|t| t.as_object()) {
+                println!("Found SWE-bench format report.json for key: {}", key);
+                found_swe_format = true;
+
+                // Parse all test categories that indicate failure
+                for (category, category_data) in tests_status {
+                    if let Some(category_obj) = category_data.as_object() {
+                        // Extract failed tests from "failure" arrays in all categories
+                        if let Some(failure_array) = category_obj.get("failure").and_then(|f| f.as_array()) {
+                            for test_item in failure_array {
+                                if let Some(test_name) = test_item.as_str() {
+                                    report_failed_tests.insert(test_name.to_string());
+                                    println!("Found failed test in category {}: {}", category, test_name);
+                                }
+                            }
+                        }
+                    }
+                }
+                break; // Found SWE-bench format, no need to check other keys
+            }
+        }
+
+        // If not SWE-bench format, try direct mapping format: {"test_name": "status"}
+        if !found_swe_format {
+            for (test_name, status_val) in obj {
+                if let Some(status) = status_val.as_str() {
+                    if is_failed(status) {
+                        report_failed_tests.insert(test_name.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    report_failed_tests
+}
+
+// C7: F2P tests found in golden source diff files but not in test diff files
+pub struct F2pInGoldenSourceDiff;
+
+impl Rule for F2pInGoldenSourceDiff {
+    fn id(&self) -> &'static str {
+        C7_F2P_TESTS_IN_GOLDEN_SOURCE_DIFF
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one F2P test name found in golden source diff files"
+    }
+
+    // The proof lives in the patch files, not in the logs
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        println!("Performing C7 check: looking for F2P tests in golden source diff files (but not in test diffs)");
+        let mut hits = vec![];
+        let mut tests = vec![];
+
+        // Find diff/patch files from patches folder
+        let diff_files: Vec<&String> = ctx.file_paths.iter()
+            .filter(|path| {
+                let path_lower = path.to_lowercase();
+                path_lower.contains("patches/") && (path_lower.ends_with(".diff") || path_lower.ends_with(".patch"))
+            })
+            .collect();
+
+        println!("Found {} diff/patch files", diff_files.len());
+
+        if diff_files.is_empty() {
+            println!("No diff/patch files found in patches folder");
+            return RuleOutcome::default();
+        }
+
+        // Separate golden source diffs from test diffs
+        let (golden_source_diffs, test_diffs): (Vec<&String>, Vec<&String>) = diff_files.iter()
+            .partition(|path| {
+                let filename = path.split('/').last().unwrap_or("").to_lowercase();
+                // Golden source diffs typically contain "gold", "golden", "src", "source"
+                // Test diffs typically contain "test"
+                (filename.contains("gold") || filename.contains("src") || filename.contains("source")) &&
+                !filename.contains("test")
+            });
+
+        println!("Found {} golden source diff files and {} test diff files",
+                 golden_source_diffs.len(), test_diffs.len());
+
+        // Read all test diff contents to check if tests appear there
+        let mut test_diff_contents = String::new();
+        for test_diff in &test_diffs {
+            if let Ok(content) = fs::read_to_string(test_diff) {
+                test_diff_contents.push_str(&content);
+                test_diff_contents.push('\n');
+                println!("Read test diff file: {}", test_diff);
+            }
+        }
+        // Normalize line endings to handle CRLF, LF, etc.
+        let normalized_test_diff = test_diff_contents.replace("\r\n", "\n").replace('\r', "\n");
+
+        // Check golden source diffs for F2P tests
+        for golden_diff in &golden_source_diffs {
+            println!("Checking golden source diff file: {}", golden_diff);
+
+            let Ok(diff_content) = fs::read_to_string(golden_diff) else {
+                println!("Failed to read golden source diff file: {}", golden_diff);
+                continue;
+            };
+            println!("Read golden source diff successfully, {} bytes", diff_content.len());
+
+            // Check if any F2P test names appear in this golden source diff
+            for f2p_test in ctx.fail_to_pass {
+                // Extract the actual test name from module path (e.g., "tests::test_example" -> "test_example")
+                let test_name_to_search = if f2p_test.contains("::") {
+                    f2p_test.split("::").last().unwrap_or(f2p_test)
+                } else {
+                    f2p_test
+                };
+                // Drop a pytest parametrization suffix ("test_parse[1-2]" -> "test_parse")
+                let test_name_to_search = test_name_to_search.split('[').next().unwrap_or(test_name_to_search);
+                let test_name_to_search = go_parent_test(test_name_to_search);
+
+                if !diff_content.contains(test_name_to_search) {
+                    continue;
+                }
+
+                // Check if this test also appears in test diffs as an actual test function
+                if defines_test_function(&normalized_test_diff, test_name_to_search) {
+                    println!("F2P test '{}' found in both golden source and test diffs as actual test function - not a violation", f2p_test);
+                } else {
+                    let violation = format!("{} (found as '{}' in {} but not as actual test function in test diffs)",
+                                          f2p_test, test_name_to_search,
+                                          golden_diff.split('/').last().unwrap_or(golden_diff));
+                    hits.push(violation);
+                    if !tests.contains(f2p_test) {
+                        tests.push(f2p_test.clone());
+                    }
+                    println!("C7 violation: F2P test '{}' found as '{}' in golden source diff '{}' but not as actual test function in test diffs",
+                             f2p_test, test_name_to_search, golden_diff);
+                }
+            }
+        }
+
+        println!("C7 check completed: {} violations found", hits.len());
+        RuleOutcome::new(hits, tests)
+    }
+}
+
+// Go subtests are declared inside their parent ("TestParse/unicode" -> "TestParse"). Only names
+// shaped like a Go test function qualify, so path-named tests ("tests/ui/foo.rs") stay whole.
+fn go_parent_test(name: &str) -> &str {
+    match name.split_once('/') {
+        Some((parent, _)) if ["Test", "Benchmark", "Fuzz"].iter().any(|prefix| parent.starts_with(prefix))
+            && parent.chars().all(|c| c.is_alphanumeric() || c == '_') => parent,
+        _ => name,
+    }
+}
+
+// C8: F2P tests whose failure in before comes from the build, a timeout or the environment
+// rather than from an assertion or panic in the test. Failures of unknown cause are not flagged.
+pub struct F2pBeforeFailureNotGenuine;
+
+impl Rule for F2pBeforeFailureNotGenuine {
+    fn id(&self) -> &'static str {
+        C8_F2P_FAILING_IN_BEFORE_FOR_WRONG_REASON
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one F2P test fails in before log because of a compile error, timeout or infrastructure problem instead of an assertion or panic"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        &["before"]
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        let mut hits = vec![];
+        let mut tests = vec![];
+        for t in ctx.fail_to_pass {
+            if ctx.before(t) != "failed" {
+                continue;
+            }
+            let Some(detail) = ctx.before_parsed.failures.get(ctx.log_name("before", t)) else { continue };
+            if detail.kind.is_genuine() || detail.kind == FailureKind::Unknown {
+                continue;
+            }
+            let kind = detail.kind.as_str();
+            hits.push(match &detail.message {
+                Some(message) => format!("{t} ({kind} in before: {})", message.lines().next().unwrap_or_default()),
+                None => format!("{t} ({kind} in before)"),
+            });
+            tests.push(t.clone());
+        }
+        RuleOutcome::new(hits, tests)
+    }
+}
+
+// C10: F2P/P2P tests that only passed after a retry. Their result depends on luck, so they
+// cannot validate the patch.
+pub struct FlakyInAnyLog;
+
+impl Rule for FlakyInAnyLog {
+    fn id(&self) -> &'static str {
+        C10_FLAKY_IN_ANY_LOG_PRESENT_IN_F2P_OR_P2P
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one F2P / P2P test was flaky (failed, then passed on retry) in a log"
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        ALL_LOGS
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        let mut hits = vec![];
+        let mut tests = vec![];
+        for t in &ctx.universe {
+            let runs: Vec<String> = ALL_LOGS.iter()
+                .filter_map(|role| ctx.parsed(role).map(|parsed| (role, parsed, ctx.log_name(role, t))))
+                .filter(|(_, parsed, name)| parsed.flaky.contains(*name))
+                .map(|(role, parsed, name)| match parsed.retries.get(name) {
+                    Some(tries) => format!("{}: {}", role, tries.join(" then ")),
+                    None => role.to_string(),
+                })
+                .collect();
+            if runs.is_empty() {
+                continue;
+            }
+            hits.push(format!("{t} (flaky in {})", runs.join("; ")));
+            tests.push(t.clone());
+        }
+        RuleOutcome::new(hits, tests)
+    }
+}
+
+// C11: F2P/P2P tests that run longer than the configured threshold in any log, which usually
+// means a hang or an environment too slow to validate with
+pub struct SlowerThanThreshold;
+
+impl Rule for SlowerThanThreshold {
+    fn id(&self) -> &'static str {
+        C11_F2P_OR_P2P_SLOWER_THAN_THRESHOLD
+    }
+
+    fn description(&self) -> &'static str {
+        "At least one F2P / P2P test runs longer than the slow test threshold"
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn evidence_logs(&self) -> &'static [&'static str] {
+        ALL_LOGS
+    }
+
+    fn evaluate(&self, ctx: &AnalysisContext) -> RuleOutcome {
+        let mut hits = vec![];
+        let mut tests = vec![];
+        for t in &ctx.universe {
+            let runs: Vec<String> = ALL_LOGS.iter()
+                .filter_map(|role| Some((role, *ctx.parsed(role)?.durations.get(ctx.log_name(role, t))?)))
+                .filter(|(_, seconds)| *seconds > ctx.slow_test_threshold)
+                .map(|(role, seconds)| format!("{}: {:.1}s", role, seconds))
+                .collect();
+            if runs.is_empty() {
+                continue;
+            }
+            hits.push(format!("{t} ({}, threshold {}s)", runs.join(", "), ctx.slow_test_threshold));
+            tests.push(t.clone());
+        }
+        RuleOutcome::new(hits, tests)
+    }
+}
+
+// Look for exact test function patterns in test diffs, with flexible whitespace/newlines
+fn defines_test_function(test_diff: &str, test_name: &str) -> bool {
+    if test_diff.is_empty() {
+        return false;
+    }
+
+    let found_direct_fn = test_diff.contains(&format!("fn {}(", test_name)) ||
+                          test_diff.contains(&format!("fn {} (", test_name)) ||
+                          test_diff.contains(&format!("def {}(", test_name)) ||
+                          test_diff.contains(&format!("func {}(", test_name));
+    if found_direct_fn {
+        return true;
+    }
+
+    // Look for #[test] attribute followed by the function
+    let lines: Vec<&str> = test_diff.lines().collect();
+    for i in 0..lines.len().saturating_sub(1) {
+        if lines[i].trim() == "#[test]" {
+            // Check next few lines for the function
+            for line in &lines[(i + 1)..min(i + 4, lines.len())] {
+                let line = line.trim();
+                if line.starts_with(&format!("fn {}(", test_name)) ||
+                   line.starts_with(&format!("fn {} (", test_name)) {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
@@ -6,7 +6,7 @@
 //! Test Flow:
 //! 1. validate_deliverable - Validates the folder structure and contents
 //! 2. download_deliverable - Downloads all required files to temp directory  
-//! 3. process_deliverable - Processes files and creates temporary structure
+//! 3. process_deliverable - Detects file roles, runs the analysis and scores the verdict
 //! 4. analyze_logs - Performs comprehensive rule-based analysis
 //!
 //! Expected Violations:
@@ -24,7 +24,11 @@ use serde::{Serialize, Deserialize};
 
 // Import the library modules we need to test
 use swe_reviewer_lib::report_checker::{validate_deliverable, download_deliverable, process_deliverable};
-use swe_reviewer_lib::analysis::analyze_logs;
+use swe_reviewer_lib::analysis::{
+    analyze_logs, AnalysisReport,
+    C1_FAILED_IN_BASE_PRESENT_IN_P2P, C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P, C3_F2P_SUCCESS_IN_BEFORE,
+    C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE, C7_F2P_TESTS_IN_GOLDEN_SOURCE_DIFF,
+};
 
 // Inline test configuration to avoid external file dependencies
 
@@ -241,7 +245,7 @@ pub struct TestResult {
     violations_found: Vec<String>,
     error: Option<String>,
     duration: Duration,
-    analysis_data: Option<serde_json::Value>,
+    analysis_data: Option<AnalysisReport>,
 }
 
 /// Test case definition
@@ -403,7 +407,7 @@ async fn execute_test_case(test_case: &TestCase, _config: &TestConfig) -> TestRe
     println!("   ⏳ Step 3: Processing deliverable...");
     let processing_result = match process_deliverable(download_result.downloaded_files).await {
         Ok(result) => {
-            println!("   ✅ Processing completed - status: {}", result.get("status").and_then(|s| s.as_str()).unwrap_or("unknown"));
+            println!("   ✅ Processing completed - status: {}, score: {}", result.status, result.score);
             result
         }
         Err(e) => {
@@ -415,20 +419,13 @@ async fn execute_test_case(test_case: &TestCase, _config: &TestConfig) -> TestRe
     };
     
     // Extract file paths from processing result
-    let file_paths = match processing_result.get("file_paths").and_then(|fp| fp.as_array()) {
-        Some(paths) => {
-            paths.iter()
-                .filter_map(|p| p.as_str())
-                .map(|s| s.to_string())
-                .collect::<Vec<String>>()
-        }
-        None => {
-            result.error = Some("No file paths found in processing result".to_string());
-            result.duration = start_time.elapsed().unwrap_or_default();
-            println!("   ❌ No file paths found in processing result");
-            return result;
-        }
-    };
+    let file_paths = processing_result.file_paths;
+    if file_paths.is_empty() {
+        result.error = Some("No file paths found in processing result".to_string());
+        result.duration = start_time.elapsed().unwrap_or_default();
+        println!("   ❌ No file paths found in processing result");
+        return result;
+    }
     
     println!("   📁 Found {} file paths for analysis", file_paths.len());
     
@@ -474,97 +471,48 @@ async fn execute_test_case(test_case: &TestCase, _config: &TestConfig) -> TestRe
 }
 
 /// Extract violations from analysis result
-fn extract_violations(analysis_result: &serde_json::Value) -> Vec<String> {
+fn extract_violations(analysis_result: &AnalysisReport) -> Vec<String> {
     let mut violations = Vec::new();
     
-    if let Some(rule_checks) = analysis_result.get("rule_checks") {
-        // Check C1: P2P failed in base
-        if let Some(c1) = rule_checks.get("c1_failed_in_base_present_in_P2P") {
-            if c1.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                violations.push("p2p_failed_in_base".to_string());
-            }
-        }
-        
-        // Check C2: Failed in after (F2P or P2P)
-        if let Some(c2) = rule_checks.get("c2_failed_in_after_present_in_F2P_or_P2P") {
-            if c2.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                if let Some(_examples) = c2.get("examples").and_then(|e| e.as_array()) {
-                    // Check if any examples are from F2P or P2P tests
-                    violations.push("tests_failed_in_after".to_string());
-                }
-            }
-        }
-        
-        // Check C3: F2P passing in before
-        if let Some(c3) = rule_checks.get("c3_F2P_success_in_before") {
-            if c3.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                violations.push("f2p_passing_in_before".to_string());
-            }
-        }
-        
-        // Check C4: P2P missing in base and not passing in before
-        if let Some(c4) = rule_checks.get("c4_P2P_missing_in_base_and_not_passing_in_before") {
-            if c4.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                violations.push("p2p_missing_in_base_and_before".to_string());
-            }
-        }
-        
-        // Check C7: F2P tests in golden source diff
-        if let Some(c7) = rule_checks.get("c7_f2p_tests_in_golden_source_diff") {
-            if c7.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                violations.push("f2p_tests_in_src_diff".to_string());
-            }
-        }
+    // Check C1: P2P failed in base
+    if analysis_result.has_problem(C1_FAILED_IN_BASE_PRESENT_IN_P2P) {
+        violations.push("p2p_failed_in_base".to_string());
     }
     
-    // Check rejection reasons for missing tests
-    if let Some(rejection) = analysis_result.get("rejection_reason") {
-        // F2P missing in after (tests considered but ok)
-        if let Some(f2p_considered_but_ok) = rejection.get("f2p_considered_but_ok").and_then(|v| v.as_array()) {
-            if !f2p_considered_but_ok.is_empty() {
-                violations.push("f2p_missing_in_after".to_string());
-            }
-        }
-        
-        // P2P missing in after - check P2P analysis for missing tests
-        if let Some(p2p_analysis) = analysis_result.get("p2p_analysis") {
-            if let Some(p2p_obj) = p2p_analysis.as_object() {
-                let mut missing_in_after = false;
-                for (_, test_data) in p2p_obj {
-                    if let Some(after_status) = test_data.get("after").and_then(|v| v.as_str()) {
-                        if after_status == "missing" {
-                            missing_in_after = true;
-                            break;
-                        }
-                    }
-                }
-                if missing_in_after {
-                    violations.push("p2p_missing_in_after".to_string());
-                }
-            }
-        }
-        
-        // P2P missing in all logs
-        if let Some(p2p_analysis) = analysis_result.get("p2p_analysis") {
-            if let Some(p2p_obj) = p2p_analysis.as_object() {
-                let mut missing_in_all = false;
-                for (_, test_data) in p2p_obj {
-                    if let Some(base_status) = test_data.get("base").and_then(|v| v.as_str()) {
-                        if let Some(before_status) = test_data.get("before").and_then(|v| v.as_str()) {
-                            if let Some(after_status) = test_data.get("after").and_then(|v| v.as_str()) {
-                                if base_status == "missing" && before_status == "missing" && after_status == "missing" {
-                                    missing_in_all = true;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-                if missing_in_all {
-                    violations.push("p2p_missing_in_all_logs".to_string());
-                }
-            }
-        }
+    // Check C2: Failed in after (F2P or P2P)
+    if analysis_result.has_problem(C2_FAILED_IN_AFTER_PRESENT_IN_F2P_OR_P2P) {
+        violations.push("tests_failed_in_after".to_string());
+    }
+    
+    // Check C3: F2P passing in before
+    if analysis_result.has_problem(C3_F2P_SUCCESS_IN_BEFORE) {
+        violations.push("f2p_passing_in_before".to_string());
+    }
+    
+    // Check C4: P2P missing in base and not passing in before
+    if analysis_result.has_problem(C4_P2P_MISSING_IN_BASE_AND_NOT_PASSING_IN_BEFORE) {
+        violations.push("p2p_missing_in_base_and_before".to_string());
+    }
+    
+    // Check C7: F2P tests in golden source diff
+    if analysis_result.has_problem(C7_F2P_TESTS_IN_GOLDEN_SOURCE_DIFF) {
+        violations.push("f2p_tests_in_src_diff".to_string());
+    }
+    
+    // F2P missing in after (tests considered but ok)
+    if !analysis_result.rejection_reason.f2p_considered_but_ok.is_empty() {
+        violations.push("f2p_missing_in_after".to_string());
+    }
+    
+    // P2P missing in after
+    if analysis_result.p2p_analysis.values().any(|row| row.after == "missing") {
+        violations.push("p2p_missing_in_after".to_string());
+    }
+    
+    // P2P missing in all logs
+    if analysis_result.p2p_analysis.values()
+        .any(|row| row.base == "missing" && row.before == "missing" && row.after == "missing") {
+        violations.push("p2p_missing_in_all_logs".to_string());
     }
     
     violations.sort();
@@ -54,16 +54,10 @@ async fn test_complete_flow_no_violations() {
     let processing_result = process_deliverable(download_result.downloaded_files).await
         .expect("Processing should succeed");
     
-    assert_eq!(processing_result.get("status").and_then(|s| s.as_str()), Some("completed"));
+    assert_eq!(processing_result.status, "completed");
+    assert!(processing_result.report.is_some(), "Should have an analysis report");
     
-    let file_paths = processing_result
-        .get("file_paths")
-        .and_then(|fp| fp.as_array())
-        .expect("Should have file paths")
-        .iter()
-        .filter_map(|p| p.as_str())
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+    let file_paths = processing_result.file_paths;
     
     assert!(!file_paths.is_empty(), "Should have file paths for analysis");
     
@@ -74,31 +68,20 @@ async fn test_complete_flow_no_violations() {
         .expect("Analysis should succeed");
     
     // Verify analysis structure
-    assert!(analysis_result.get("rule_checks").is_some(), "Should have rule checks");
-    assert!(analysis_result.get("p2p_analysis").is_some(), "Should have P2P analysis");
-    assert!(analysis_result.get("f2p_analysis").is_some(), "Should have F2P analysis");
+    assert!(!analysis_result.rule_checks.is_empty(), "Should have rule checks");
+    assert_eq!(analysis_result.p2p_analysis.len(), analysis_result.counts.p2p, "Should have P2P analysis");
+    assert_eq!(analysis_result.f2p_analysis.len(), analysis_result.counts.f2p, "Should have F2P analysis");
     
     println!("✅ Analysis passed - rule checks completed");
     
     // For no-violations case, we expect minimal rule violations
-    if let Some(rule_checks) = analysis_result.get("rule_checks") {
-        let violations: Vec<String> = rule_checks.as_object().unwrap_or(&serde_json::Map::new())
-            .iter()
-            .filter_map(|(name, data)| {
-                if data.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                    Some(name.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        println!("📊 Rule violations found: {:?}", violations);
-        
-        // For this specific test case, we expect no major violations
-        // Some minor issues might be acceptable depending on the data
-        assert!(violations.len() <= 2, "Should have minimal violations for no-violation test case");
-    }
+    let violations = analysis_result.violated_rules();
+    
+    println!("📊 Rule violations found: {:?}", violations);
+    
+    // For this specific test case, we expect no major violations
+    // Some minor issues might be acceptable depending on the data
+    assert!(violations.len() <= 2, "Should have minimal violations for no-violation test case");
     
     println!("🎉 Complete flow test passed!");
 }
@@ -138,43 +121,25 @@ async fn test_f2p_violation_case() {
     let processing_result = process_deliverable(download_result.downloaded_files).await
         .expect("Processing should succeed");
     
-    let file_paths = processing_result
-        .get("file_paths")
-        .and_then(|fp| fp.as_array())
-        .expect("Should have file paths")
-        .iter()
-        .filter_map(|p| p.as_str())
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+    let file_paths = processing_result.file_paths;
     
     let analysis_result = analyze_logs(file_paths).await
         .expect("Analysis should succeed");
     
     // Check for expected violations
-    if let Some(rule_checks) = analysis_result.get("rule_checks") {
-        let violations: Vec<String> = rule_checks.as_object().unwrap_or(&serde_json::Map::new())
-            .iter()
-            .filter_map(|(name, data)| {
-                if data.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                    Some(name.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        println!("📊 Rule violations found: {:?}", violations);
-        
-        // Should have some violations for this test case
-        assert!(!violations.is_empty(), "Should have violations for F2P violation test case");
-        
-        // Check for specific F2P-related violations
-        let f2p_violations = violations.iter()
-            .filter(|v| v.contains("F2P") || v.contains("f2p"))
-            .count();
-        
-        println!("🔍 F2P-related violations: {}", f2p_violations);
-    }
+    let violations = analysis_result.violated_rules();
+    
+    println!("📊 Rule violations found: {:?}", violations);
+    
+    // Should have some violations for this test case
+    assert!(!violations.is_empty(), "Should have violations for F2P violation test case");
+    
+    // Check for specific F2P-related violations
+    let f2p_violations = violations.iter()
+        .filter(|v| v.contains("F2P") || v.contains("f2p"))
+        .count();
+    
+    println!("🔍 F2P-related violations: {}", f2p_violations);
 }
 
 /// Test P2P violation case
@@ -197,41 +162,23 @@ async fn test_p2p_violation_case() {
     let processing_result = process_deliverable(download_result.downloaded_files).await
         .expect("Processing should succeed");
     
-    let file_paths = processing_result
-        .get("file_paths")
-        .and_then(|fp| fp.as_array())
-        .expect("Should have file paths")
-        .iter()
-        .filter_map(|p| p.as_str())
-        .map(|s| s.to_string())
-        .collect::<Vec<String>>();
+    let file_paths = processing_result.file_paths;
     
     let analysis_result = analyze_logs(file_paths).await
         .expect("Analysis should succeed");
     
     // Check for P2P violations
-    if let Some(rule_checks) = analysis_result.get("rule_checks") {
-        let violations: Vec<String> = rule_checks.as_object().unwrap_or(&serde_json::Map::new())
-            .iter()
-            .filter_map(|(name, data)| {
-                if data.get("has_problem").and_then(|v| v.as_bool()).unwrap_or(false) {
-                    Some(name.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
-        
-        println!("📊 Rule violations found: {:?}", violations);
-        
-        // Check for P2P-related violations
-        let p2p_violations = violations.iter()
-            .filter(|v| v.contains("P2P") || v.contains("p2p") || v.contains("base"))
-            .count();
-        
-        println!("🔍 P2P-related violations: {}", p2p_violations);
-        assert!(p2p_violations > 0, "Should have P2P violations for this test case");
-    }
+    let violations = analysis_result.violated_rules();
+    
+    println!("📊 Rule violations found: {:?}", violations);
+    
+    // Check for P2P-related violations
+    let p2p_violations = violations.iter()
+        .filter(|v| v.contains("P2P") || v.contains("p2p") || v.contains("base"))
+        .count();
+    
+    println!("🔍 P2P-related violations: {}", p2p_violations);
+    assert!(p2p_violations > 0, "Should have P2P violations for this test case");
 }
 
 /// Benchmark test to measure performance
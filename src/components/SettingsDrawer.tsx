@@ -12,6 +12,7 @@ export default function SettingsDrawer({ isOpen, onClose, onLogout, isAuthentica
   const [googleClientSecret, setGoogleClientSecret] = useState("");
   const [geminiApiKey, setGeminiApiKey] = useState("");
   const [openaiApiKey, setOpenaiApiKey] = useState("");
+  const [slowTestThreshold, setSlowTestThreshold] = useState("");
   const [isLoading, setIsLoading] = useState(false);
   const [saveSuccess, setSaveSuccess] = useState(false);
   const [tempDirSize, setTempDirSize] = useState<number>(0);
@@ -30,9 +31,11 @@ export default function SettingsDrawer({ isOpen, onClose, onLogout, isAuthentica
       const clientSecret = await invoke<string>("load_setting", { key: "google_client_secret" });
       const geminiKey = await invoke<string>("load_setting", { key: "gemini_api_key" });
       const openaiKey = await invoke<string>("load_setting", { key: "openai_api_key" });
+      const threshold = await invoke<string>("load_setting", { key: "slow_test_threshold_secs" });
       setGoogleClientSecret(clientSecret || "");
       setGeminiApiKey(geminiKey || "");
       setOpenaiApiKey(openaiKey || "");
+      setSlowTestThreshold(threshold || "");
     } catch (error) {
       console.error("Failed to load settings:", error);
     }
@@ -64,6 +67,10 @@ export default function SettingsDrawer({ isOpen, onClose, onLogout, isAuthentica
         key: "openai_api_key", 
         value: openaiApiKey 
       });
+      await invoke("save_setting", { 
+        key: "slow_test_threshold_secs", 
+        value: slowTestThreshold 
+      });
       setSaveSuccess(true);
       setTimeout(() => setSaveSuccess(false), 2000);
     } catch (error) {
@@ -201,6 +208,24 @@ export default function SettingsDrawer({ isOpen, onClose, onLogout, isAuthentica
               </p>
             </div>
 
+            {/* Slow Test Threshold */}
+            <div className="space-y-3">
+              <label className="block text-sm font-medium text-gray-700 dark:text-gray-300">
+                Slow Test Threshold (seconds)
+              </label>
+              <input
+                type="number"
+                min="0"
+                value={slowTestThreshold}
+                onChange={(e) => setSlowTestThreshold(e.target.value)}
+                placeholder="60"
+                className="w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md bg-white dark:bg-gray-700 text-gray-900 dark:text-white placeholder-gray-500 dark:placeholder-gray-400 focus:outline-none focus:ring-2 focus:ring-blue-500 focus:border-transparent"
+              />
+              <p className="text-xs text-gray-500 dark:text-gray-400">
+                F2P / P2P tests that run longer than this in any log are flagged in the analysis.
+              </p>
+            </div>
+
             {/* Save Button */}
             <div className="flex flex-col space-y-2">
               <button
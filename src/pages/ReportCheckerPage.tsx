@@ -35,6 +35,8 @@ interface ProcessingResult {
   score: number;
   file_paths?: string[];
   analysis_files?: string[];
+  decision?: "accept" | "reject" | "needs_manual_review" | null;
+  summary?: string;
 }
 
 interface FileContent {
@@ -152,10 +154,21 @@ export default function ReportCheckerPage() {
       before: analysis.before || "missing", 
       after: analysis.after || "missing",
       agent: analysis.agent || "missing",
-      report: analysis.report || "missing"
+      report: analysis.report || "missing",
+      failures: (analysis.failures || {}) as Record<string, { location?: string | null; message?: string | null }>,
+      doctest: Boolean(analysis.doctest)
     };
   };
 
+  // Hover text with the panic site and message of each log the test failed in
+  const describeFailures = (testStatus: ReturnType<typeof getTestStatus>) => {
+    if (!testStatus) return undefined;
+    const lines = Object.entries(testStatus.failures).map(([log, detail]) =>
+      `${log}: ${detail.message || "failed"}${detail.location ? ` (${detail.location})` : ""}`
+    );
+    return lines.length > 0 ? lines.join("\n") : undefined;
+  };
+
   // Helper function to check if test has rule violations
   const hasRuleViolations = (testName: string, testType: "f2p" | "p2p") => {
     if (!analysisResult) return false;
@@ -249,7 +262,7 @@ export default function ReportCheckerPage() {
           });
           
           if (isViolated) {
-            const errorMessage = errorMessageMap[ruleKey];
+            const errorMessage = errorMessageMap[ruleKey] ?? rule.description;
             if (errorMessage) {
               errorMessages.push(errorMessage);
             }
@@ -1211,15 +1224,8 @@ export default function ReportCheckerPage() {
       const downloadData = await invoke("download_deliverable", { filesToDownload: validationData.files_to_download, folderId: validationData.folder_id }) as DownloadResult;
       updateStageStatus("downloading", "completed");
 
-      // Create a simple result with the downloaded files
-      const result: ProcessingResult = {
-        status: "downloaded",
-        message: "Files downloaded successfully. Click Analyze to process them.",
-        files_processed: downloadData.downloaded_files.length,
-        issues_found: 0,
-        score: 0,
-        file_paths: downloadData.downloaded_files.map(file => file.path)
-      };
+      // Run role detection, analysis and scoring on the downloaded files
+      const result = await invoke("process_deliverable", { downloadedFiles: downloadData.downloaded_files }) as ProcessingResult;
 
       setResult(result);
       setActiveMainTab("manual_checker");
@@ -1572,7 +1578,12 @@ export default function ReportCheckerPage() {
                               <span className="w-8 text-right pr-2 text-gray-400 dark:text-gray-500 flex-shrink-0 font-mono text-xs">
                                 {index + 1}
                               </span>
-                              <span className="flex-1 truncate">{test}</span>
+                              <span className="flex-1 truncate" title={describeFailures(testStatus) ?? test}>{test}</span>
+                              {testStatus?.doctest && (
+                                <span className="ml-1 inline-flex items-center px-1.5 rounded-full text-xs font-medium bg-purple-100 text-purple-700 dark:bg-purple-900/50 dark:text-purple-300 flex-shrink-0">
+                                  doctest
+                                </span>
+                              )}
                               <div className="flex items-center gap-1 ml-2 flex-shrink-0">
                                 {testStatus && (
                                   <>
@@ -1646,7 +1657,12 @@ export default function ReportCheckerPage() {
                               <span className="w-8 text-right pr-2 text-gray-400 dark:text-gray-500 flex-shrink-0 font-mono text-xs">
                                 {index + 1}
                               </span>
-                              <span className="flex-1 truncate">{test}</span>
+                              <span className="flex-1 truncate" title={describeFailures(testStatus) ?? test}>{test}</span>
+                              {testStatus?.doctest && (
+                                <span className="ml-1 inline-flex items-center px-1.5 rounded-full text-xs font-medium bg-purple-100 text-purple-700 dark:bg-purple-900/50 dark:text-purple-300 flex-shrink-0">
+                                  doctest
+                                </span>
+                              )}
                               <div className="flex items-center gap-1 ml-2 flex-shrink-0">
                                 {testStatus && (
                                   <>